@@ -1,6 +1,6 @@
 use remote_hdt::error::RemoteHDTError;
 use remote_hdt::storage::layout::tabular::TabularLayout;
-use remote_hdt::storage::params::{Backend, Serialization};
+use remote_hdt::storage::params::{Backend, Serialization, ThreadingStrategy};
 use remote_hdt::storage::Storage;
 use std::env;
 use std::time::Instant;
@@ -16,8 +16,10 @@ fn main() -> Result<(), RemoteHDTError> {
 
     let before = Instant::now();
 
-    Storage::new(TabularLayout, Serialization::Zarr)
-        .load(Backend::FileSystem(format!("{}.zarr", zarr_path).as_str()))?;
+    Storage::new(TabularLayout, Serialization::Zarr).load(
+        Backend::FileSystem(format!("{}.zarr", zarr_path).as_str()),
+        ThreadingStrategy::Multi(0),
+    )?;
 
     println!("Elapsed time: {:.2?}", before.elapsed());
 
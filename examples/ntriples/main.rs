@@ -1,6 +1,8 @@
 use remote_hdt::error::RemoteHDTError;
 use remote_hdt::storage::layout::tabular::TabularLayout;
-use remote_hdt::storage::params::{Backend, ChunkingStrategy, ReferenceSystem, Serialization};
+use remote_hdt::storage::params::{
+    Backend, ChunkingStrategy, ReferenceSystem, Serialization, ThreadingStrategy,
+};
 use remote_hdt::storage::Storage;
 
 pub fn main() -> Result<(), RemoteHDTError> {
@@ -9,6 +11,7 @@ pub fn main() -> Result<(), RemoteHDTError> {
         "examples/ntriples/rdf.nt",
         ChunkingStrategy::Chunk,
         ReferenceSystem::SPO,
+        ThreadingStrategy::Multi(0),
     )?;
 
     Ok(())
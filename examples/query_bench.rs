@@ -1,7 +1,7 @@
 use remote_hdt::error::RemoteHDTError;
 use remote_hdt::storage::layout::matrix::MatrixLayout;
 use remote_hdt::storage::ops::Ops;
-use remote_hdt::storage::params::{Backend, Serialization};
+use remote_hdt::storage::params::{Backend, Serialization, ThreadingStrategy};
 use remote_hdt::storage::Storage;
 use std::env;
 use std::time::Instant;
@@ -18,7 +18,10 @@ fn main() -> Result<(), RemoteHDTError> {
     let zarr_path = format!("{}-lubm", number_of_universities);
 
     let mut binding = Storage::new(MatrixLayout, Serialization::Zarr);
-    let arr = binding.load(Backend::FileSystem(format!("{}.zarr", zarr_path).as_str()))?;
+    let arr = binding.load(
+        Backend::FileSystem(format!("{}.zarr", zarr_path).as_str()),
+        ThreadingStrategy::Multi(0),
+    )?;
 
     let before = Instant::now();
     arr.get_object(SUBJECT)?;
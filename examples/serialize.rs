@@ -1,6 +1,8 @@
 use remote_hdt::error::RemoteHDTError;
 use remote_hdt::storage::layout::matrix::MatrixLayout;
-use remote_hdt::storage::params::{Backend, ChunkingStrategy, ReferenceSystem, Serialization};
+use remote_hdt::storage::params::{
+    Backend, ChunkingStrategy, ReferenceSystem, Serialization, ThreadingStrategy,
+};
 use remote_hdt::storage::Storage;
 use std::env;
 use std::time::Instant;
@@ -22,6 +24,7 @@ fn main() -> Result<(), RemoteHDTError> {
         rdf_path,
         ChunkingStrategy::Sharding(*shard_size),
         ReferenceSystem::SPO,
+        ThreadingStrategy::Multi(0),
     )?;
 
     println!("Elapsed time: {:.2?}", before.elapsed());
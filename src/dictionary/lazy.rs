@@ -0,0 +1,280 @@
+use fcsd::Set;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use zarrs::array::Array;
+use zarrs::array_subset::ArraySubset;
+use zarrs::storage::ReadableStorageTraits;
+
+use crate::error::RemoteHDTError;
+use crate::storage::StorageResult;
+use crate::utils::DICTIONARY_CHUNK_SIZE;
+
+const SERIAL_COOKIE: u32 = 114514;
+const END_MARKER: u8 = 0;
+
+type Block = Arc<Vec<u8>>;
+
+/// A front-coded set of terms that is not loaded in memory. Only its header and
+/// the pointers to each of the buckets are read when it is created, while the
+/// buckets themselves are fetched from the store on demand. As every read is
+/// performed over a range of the array, the store is asked only for the chunks
+/// that are needed; that is, through ranged requests in the case of HTTP. The
+/// chunks that have been retrieved are cached, so they are fetched only once.
+///
+/// The layout of the array is the one that [`Set::serialize_into`] produces.
+#[derive(Clone)]
+pub(crate) struct LazySet {
+    array: Arc<Array<dyn ReadableStorageTraits>>,
+    pointers: Vec<u64>,
+    serialized_offset: u64,
+    serialized_len: u64,
+    len: usize,
+    bucket_bits: usize,
+    bucket_mask: usize,
+    blocks: Arc<Mutex<HashMap<u64, Block>>>,
+}
+
+impl LazySet {
+    pub(crate) fn new(store: Arc<dyn ReadableStorageTraits>, path: &str) -> StorageResult<Self> {
+        let mut set = LazySet {
+            array: Arc::new(Array::new(store, path)?),
+            pointers: Vec::new(),
+            serialized_offset: 0,
+            serialized_len: 0,
+            len: 0,
+            bucket_bits: 0,
+            bucket_mask: 0,
+            blocks: Default::default(),
+        };
+
+        // 1. The header is composed of a cookie followed by the pointers to the
+        // start of every bucket, which are stored as a vector of packed integers
+        if set.read_u32(0)? != SERIAL_COOKIE {
+            return Err(RemoteHDTError::DictionaryDeserialization);
+        }
+        let words = set.read_u64(4)?;
+        if words > set.array.shape()[0] / 8 {
+            return Err(RemoteHDTError::DictionaryDeserialization);
+        }
+        let words_offset = 12;
+        let pointers_len = set.read_u64(words_offset + 8 * words)? as usize;
+        let bits = set.read_u64(words_offset + 8 * words + 8)? as usize;
+        let mask = set.read_u64(words_offset + 8 * words + 16)?;
+        if bits > 64 {
+            return Err(RemoteHDTError::DictionaryDeserialization);
+        }
+        let chunks = set
+            .read(words_offset..words_offset + 8 * words)?
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        set.pointers = (0..pointers_len)
+            .map(|i| unpack(&chunks, bits, mask, i))
+            .collect::<Option<_>>()
+            .ok_or(RemoteHDTError::DictionaryDeserialization)?;
+
+        // 2. Then, the front-coded terms are stored, preceded by their length
+        let serialized_len_offset = words_offset + 8 * words + 24;
+        set.serialized_len = set.read_u64(serialized_len_offset)?;
+        set.serialized_offset = serialized_len_offset + 8;
+        if set.serialized_len > set.array.shape()[0] {
+            return Err(RemoteHDTError::DictionaryDeserialization);
+        }
+
+        // 3. Lastly, the number of terms and the size of the buckets are stored
+        let trailer_offset = set.serialized_offset + set.serialized_len;
+        set.len = set.read_u64(trailer_offset)? as usize;
+        set.bucket_bits = set.read_u64(trailer_offset + 8)? as usize;
+        set.bucket_mask = set.read_u64(trailer_offset + 16)? as usize;
+        if set.bucket_bits >= usize::BITS as usize || set.bucket_mask != (1 << set.bucket_bits) - 1
+        {
+            return Err(RemoteHDTError::DictionaryDeserialization);
+        }
+
+        Ok(set)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The index of a term in the set, if it is in it.
+    ///
+    /// # Errors
+    /// Returns an error if the buckets cannot be fetched from the store, or
+    /// if they are not serialized properly.
+    pub(crate) fn locate(&self, term: &str) -> StorageResult<Option<usize>> {
+        let term = term.as_bytes();
+
+        // We look for the last bucket whose header is less than or equal to the
+        // provided term; that is, the only one that may contain it. Only the
+        // buckets visited by the binary search are fetched from the store
+        let (mut lo, mut hi) = (0, self.pointers.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            match self.bucket(mid)?.first() {
+                Some(header) if header.as_slice() <= term => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+
+        let bucket_id = match lo.checked_sub(1) {
+            Some(bucket_id) => bucket_id,
+            None => return Ok(None),
+        };
+        Ok(self
+            .bucket(bucket_id)?
+            .iter()
+            .position(|key| key.as_slice() == term)
+            .map(|position| (bucket_id << self.bucket_bits) + position))
+    }
+
+    /// The term at an index of the set, if there is such.
+    ///
+    /// # Errors
+    /// Returns an error if its bucket cannot be fetched from the store, or if
+    /// it is not serialized properly.
+    pub(crate) fn decode(&self, index: usize) -> StorageResult<Option<String>> {
+        if index >= self.len {
+            return Ok(None);
+        }
+        let bucket = self.bucket(index >> self.bucket_bits)?;
+        match bucket.get(index & self.bucket_mask) {
+            Some(key) => match String::from_utf8(key.to_owned()) {
+                Ok(term) => Ok(Some(term)),
+                Err(_) => Err(RemoteHDTError::DictionaryDeserialization),
+            },
+            None => Err(RemoteHDTError::DictionaryDeserialization),
+        }
+    }
+
+    pub(crate) fn to_set(&self) -> StorageResult<Set> {
+        let bytes = self.read(0..self.array.shape()[0])?;
+        match Set::deserialize_from(bytes.as_slice()) {
+            Ok(set) => Ok(set),
+            Err(_) => Err(RemoteHDTError::DictionaryDeserialization),
+        }
+    }
+
+    fn bucket(&self, bucket_id: usize) -> StorageResult<Vec<Vec<u8>>> {
+        let start = match self.pointers.get(bucket_id) {
+            Some(&start) => start,
+            None => return Ok(Vec::new()),
+        };
+        let end = match self.pointers.get(bucket_id + 1) {
+            Some(&end) => end,
+            None => self.serialized_len,
+        };
+        let bytes = self.read(self.serialized_offset + start..self.serialized_offset + end)?;
+
+        // The first term in the bucket is stored as is, while the following ones
+        // are stored as the length of the prefix they share with the previous
+        // term, followed by the remaining suffix. All of them end with a marker.
+        // Truncated or corrupt buckets are reported instead of panicking
+        let size = match self.len.checked_sub(bucket_id << self.bucket_bits) {
+            Some(remaining) => (self.bucket_mask + 1).min(remaining),
+            None => return Err(RemoteHDTError::DictionaryDeserialization),
+        };
+        let mut keys: Vec<Vec<u8>> = Vec::with_capacity(size);
+        let mut pos = 0;
+        for _ in 0..size {
+            let mut key = match keys.last() {
+                None => Vec::new(),
+                Some(previous) => {
+                    let (lcp, read) = vbyte(bytes.get(pos..).unwrap_or_default())?;
+                    pos += read;
+                    match previous.get(..lcp) {
+                        Some(prefix) => prefix.to_vec(),
+                        None => return Err(RemoteHDTError::DictionaryDeserialization),
+                    }
+                }
+            };
+            let suffix = bytes.get(pos..).unwrap_or_default();
+            let suffix_len = match suffix.iter().position(|&b| b == END_MARKER) {
+                Some(suffix_len) => suffix_len,
+                None => return Err(RemoteHDTError::DictionaryDeserialization),
+            };
+            key.extend_from_slice(&suffix[..suffix_len]);
+            pos += suffix_len + 1;
+            keys.push(key);
+        }
+
+        Ok(keys)
+    }
+
+    fn read(&self, range: Range<u64>) -> StorageResult<Vec<u8>> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        if range.end > self.array.shape()[0] {
+            return Err(RemoteHDTError::DictionaryDeserialization);
+        }
+        let mut bytes = Vec::with_capacity((range.end - range.start) as usize);
+        for block_id in
+            range.start / DICTIONARY_CHUNK_SIZE..=(range.end - 1) / DICTIONARY_CHUNK_SIZE
+        {
+            let block = self.block(block_id)?;
+            let block_start = block_id * DICTIONARY_CHUNK_SIZE;
+            let from = (range.start.max(block_start) - block_start) as usize;
+            let to = (range.end.min(block_start + block.len() as u64) - block_start) as usize;
+            bytes.extend_from_slice(&block[from..to]);
+        }
+        Ok(bytes)
+    }
+
+    fn block(&self, block_id: u64) -> StorageResult<Block> {
+        if let Some(block) = self.blocks.lock().get(&block_id) {
+            return Ok(block.clone());
+        }
+
+        // Blocks are aligned with the inner chunks of the shards, so that only
+        // one of them has to be fetched and decompressed from the store
+        let start = block_id * DICTIONARY_CHUNK_SIZE;
+        let end = (start + DICTIONARY_CHUNK_SIZE).min(self.array.shape()[0]);
+        let block = Arc::new(self.array.retrieve_array_subset_elements::<u8>(
+            &ArraySubset::new_with_start_shape(vec![start], vec![end - start])?,
+        )?);
+        self.blocks.lock().insert(block_id, block.clone());
+
+        Ok(block)
+    }
+
+    fn read_u32(&self, offset: u64) -> StorageResult<u32> {
+        let bytes = self.read(offset..offset + 4)?;
+        match bytes.try_into() {
+            Ok(bytes) => Ok(u32::from_le_bytes(bytes)),
+            Err(_) => Err(RemoteHDTError::DictionaryDeserialization),
+        }
+    }
+
+    fn read_u64(&self, offset: u64) -> StorageResult<u64> {
+        let bytes = self.read(offset..offset + 8)?;
+        match bytes.try_into() {
+            Ok(bytes) => Ok(u64::from_le_bytes(bytes)),
+            Err(_) => Err(RemoteHDTError::DictionaryDeserialization),
+        }
+    }
+}
+
+fn unpack(chunks: &[u64], bits: usize, mask: u64, i: usize) -> Option<u64> {
+    let (q, m) = ((i.checked_mul(bits)?) / 64, (i * bits) % 64);
+    if m + bits <= 64 {
+        Some((chunks.get(q)? >> m) & mask)
+    } else {
+        Some(((chunks.get(q)? >> m) | (chunks.get(q + 1)? << (64 - m))) & mask)
+    }
+}
+
+fn vbyte(bytes: &[u8]) -> StorageResult<(usize, usize)> {
+    let mut value = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        let shifted = ((byte & 127) as usize).checked_shl(7 * i as u32);
+        value |= shifted.ok_or(RemoteHDTError::DictionaryDeserialization)?;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(RemoteHDTError::DictionaryDeserialization)
+}
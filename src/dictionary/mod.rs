@@ -0,0 +1,897 @@
+use fcsd::builder::Builder;
+use fcsd::decoder::Decoder;
+use fcsd::Set;
+use fcsd::DEFAULT_BUCKET_SIZE;
+use std::cmp::Ordering;
+use std::ops::Bound;
+use std::ops::Range;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+
+use crate::error::ParserError;
+use crate::io::formatter::parse_term;
+use crate::io::formatter::ParsedTerm;
+use crate::storage::params::DictionaryFormat;
+use crate::storage::params::ReferenceSystem;
+use crate::storage::StorageResult;
+
+use self::lazy::LazySet;
+use self::partition::cmp_terms;
+use self::partition::parse_integer;
+use self::partition::Partition;
+use self::partition::Partitioner;
+use self::partition::TermKind;
+
+pub(crate) mod lazy;
+pub mod partition;
+
+/// A section of the Dictionary, next to the kind of its terms if they are
+/// stored without the part that their kind already tells
+type Section<'a> = (Option<&'a TermKind>, &'a Terms);
+
+#[derive(Clone)]
+pub struct Dictionary {
+    reference_system: ReferenceSystem,
+    // The terms that are both subjects and objects, when they are kept in a
+    // section of their own; in that case, the subjects and objects sections
+    // only hold the rest of them
+    shared: Option<Terms>,
+    subjects: Terms,
+    predicates: Terms,
+    // The objects that are not shared, split by their kind
+    objects: Vec<Partition>,
+    graphs: Option<Set>,
+}
+
+/// The terms of a certain field of the Dictionary. They are either fully
+/// loaded in memory, or fetched bucket by bucket from the store when needed.
+/// The subjects may also be empty if all of them are shared, as front-coded
+/// sets cannot be empty, while the integer literals are kept as such.
+#[derive(Clone)]
+pub(crate) enum Terms {
+    Loaded(Set),
+    Lazy(LazySet),
+    Integers(Arc<Vec<i64>>),
+    Empty,
+}
+
+/// The terms of a field of the Dictionary, in ascending order of their indices;
+/// that is, the term at a position is the one whose index is that position,
+/// once the offset of the field in the array is subtracted. It borrows the
+/// sections of the field as they are stored, so creating it is cheap, and the
+/// terms of the lazy sections are only fetched when they are needed.
+pub struct TermsView<'a> {
+    sections: Vec<Section<'a>>,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary {
+            reference_system: ReferenceSystem::SPO,
+            shared: None,
+            subjects: Terms::Loaded(Set::new(vec!["PlaceHolder"]).unwrap()),
+            predicates: Terms::Loaded(Set::new(vec!["PlaceHolder"]).unwrap()),
+            objects: vec![Partition {
+                kind: Some(TermKind::Iri),
+                terms: Terms::Loaded(Set::new(vec!["PlaceHolder"]).unwrap()),
+            }],
+            graphs: None,
+        }
+    }
+}
+
+impl Dictionary {
+    /// Creates a Dictionary from the terms of every field, which must be unique
+    /// and sorted: the subjects as strings, and the objects by
+    /// [`partition::cmp_terms`], which is the same order for IRIs and blank
+    /// nodes. Both are consumed at once, as in the merge step of a merge sort;
+    /// thus, the terms that are both subjects and objects are found without
+    /// collecting them, and moved into the shared section if the four-section
+    /// format is requested. The objects are split into partitions meanwhile.
+    pub(crate) fn from_terms(
+        reference_system: ReferenceSystem,
+        format: DictionaryFormat,
+        subjects: impl IntoIterator<Item = impl AsRef<str>>,
+        predicates: Set,
+        objects: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Self, ParserError> {
+        let (mut shared, mut subjects_only) = (None, None);
+        let mut objects_only = Partitioner::default();
+        let mut subjects = subjects.into_iter().peekable();
+        let mut objects = objects.into_iter().peekable();
+        loop {
+            let ordering = match (subjects.peek(), objects.peek()) {
+                (Some(subject), Some(object)) => cmp_terms(subject.as_ref(), object.as_ref()),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            let subject = match ordering {
+                Ordering::Greater => None,
+                _ => subjects.next(),
+            };
+            let object = match ordering {
+                Ordering::Less => None,
+                _ => objects.next(),
+            };
+            match (subject, object) {
+                (Some(subject), Some(_)) if format == DictionaryFormat::FourSection => {
+                    add_to_section(&mut shared, subject.as_ref())?
+                }
+                (subject, object) => {
+                    if let Some(subject) = subject {
+                        add_to_section(&mut subjects_only, subject.as_ref())?;
+                    }
+                    if let Some(object) = object {
+                        objects_only.push(object.as_ref())?;
+                    }
+                }
+            }
+        }
+
+        Ok(Self::from_sections(
+            reference_system,
+            to_terms(shared),
+            to_terms(subjects_only),
+            predicates.into(),
+            objects_only.finish(),
+        ))
+    }
+
+    /// Creates a Dictionary from the sections of the four-section format, in
+    /// which the subjects and objects sections do not hold the shared terms.
+    /// If there are no shared terms, it is the same as the three-section one.
+    pub(crate) fn from_sections(
+        reference_system: ReferenceSystem,
+        shared: Terms,
+        subjects: Terms,
+        predicates: Terms,
+        objects: Vec<Partition>,
+    ) -> Self {
+        Dictionary {
+            reference_system,
+            shared: match shared {
+                Terms::Empty => None,
+                shared => Some(shared),
+            },
+            subjects,
+            predicates,
+            objects,
+            graphs: None,
+        }
+    }
+
+    /// The names of the graphs that are stored in partitions of their own,
+    /// which are always loaded in memory, as there are usually few of them
+    pub(crate) fn with_graphs(mut self, graphs: Set) -> Self {
+        self.graphs = Some(graphs);
+        self
+    }
+
+    pub fn subjects_size(&self) -> usize {
+        sections_len(&self.subject_sections())
+    }
+
+    pub fn predicates_size(&self) -> usize {
+        self.predicates.len()
+    }
+
+    pub fn objects_size(&self) -> usize {
+        sections_len(&self.object_sections())
+    }
+
+    /// The number of terms that are both subjects and objects, which is 0
+    /// unless the dataset was serialized using [`DictionaryFormat::FourSection`].
+    /// These terms have the first indices both as subjects and as objects.
+    pub fn shared_size(&self) -> usize {
+        self.shared.as_ref().map_or(0, Terms::len)
+    }
+
+    /// The number of named graphs, which is 0 if the dataset was serialized
+    /// merging all of them into the default graph
+    pub fn graphs_size(&self) -> usize {
+        match &self.graphs {
+            Some(graphs) => graphs.len(),
+            None => 0,
+        }
+    }
+
+    /// The subjects, in ascending order of their indices; see [`TermsView`].
+    pub fn subjects(&self) -> TermsView<'_> {
+        TermsView {
+            sections: self.subject_sections(),
+        }
+    }
+
+    /// The predicates, in ascending order of their indices; see [`TermsView`].
+    pub fn predicates(&self) -> TermsView<'_> {
+        TermsView {
+            sections: self.predicate_sections(),
+        }
+    }
+
+    /// The objects, in ascending order of their indices; see [`TermsView`].
+    pub fn objects(&self) -> TermsView<'_> {
+        TermsView {
+            sections: self.object_sections(),
+        }
+    }
+
+    /// The sections of the subjects and predicates as they are stored, the
+    /// shared one being the first; empty sections are `None`, as they are not
+    /// stored at all. The objects are stored partition by partition.
+    pub(crate) fn sections(&self) -> StorageResult<[Option<Set>; 3]> {
+        let shared = match &self.shared {
+            Some(shared) => shared.to_set()?,
+            None => None,
+        };
+        Ok([shared, self.subjects.to_set()?, self.predicates.to_set()?])
+    }
+
+    pub(crate) fn partitions(&self) -> &[Partition] {
+        &self.objects
+    }
+
+    /// The kinds of the objects, next to the range of indices of the objects
+    /// of each kind, in ascending order. The literals come after the IRIs and
+    /// blank nodes, grouped by their language tag or datatype. In the
+    /// four-section format, the shared IRIs and blank nodes come first. In
+    /// datasets whose objects are not partitioned, the runs of objects of the
+    /// same kind are found by going through all of them.
+    pub fn object_partitions(&self) -> Vec<(TermKind, Range<usize>)> {
+        let mut start = self.objects_offset();
+        let mut partitions = Vec::new();
+        if let Some(shared) = &self.shared {
+            // The shared terms are sorted; hence, IRIs go before blank nodes
+            let blank_nodes = shared.partition_point(|term| term < "_:");
+            partitions.push((TermKind::Iri, start..start + blank_nodes));
+            partitions.push((
+                TermKind::BlankNode,
+                start + blank_nodes..start + shared.len(),
+            ));
+            start += shared.len();
+        }
+        for partition in &self.objects {
+            let end = start + partition.terms.len();
+            match &partition.kind {
+                Some(kind) => partitions.push((kind.to_owned(), start..end)),
+                None => iter_section(&partition.terms, start).for_each(|(index, term)| {
+                    let kind = TermKind::of(&term);
+                    match partitions.last_mut() {
+                        Some((last, range)) if *last == kind => range.end = index + 1,
+                        _ => partitions.push((kind, index..index + 1)),
+                    }
+                }),
+            }
+            start = end;
+        }
+        partitions.retain(|(_, range)| !range.is_empty());
+        partitions
+    }
+
+    /// The range of indices of the objects that are literals, which are kept
+    /// next to each other; thus, queries can be restricted to them. They are
+    /// the last ones, unless the objects are not partitioned, in which case
+    /// they are the first ones, as they are sorted as strings.
+    pub fn literals(&self) -> Range<usize> {
+        let end = self.objects_offset() + self.objects_size();
+        self.object_partitions()
+            .into_iter()
+            .filter(|(kind, _)| kind.is_literal())
+            .map(|(_, range)| range)
+            .reduce(|first, last| first.start..last.end)
+            .unwrap_or(end..end)
+    }
+
+    /// Returns the range of indices of the objects of an integer datatype whose
+    /// values are within the provided bounds, as they are sorted by their
+    /// value. It is `None` if there are no literals of the datatype stored as
+    /// integers, which happens if any of them is not in its canonical form.
+    pub fn get_integer_range(
+        &self,
+        datatype: &str,
+        bounds: impl RangeBounds<i64>,
+    ) -> Option<Range<usize>> {
+        let mut start = self.objects_offset() + self.shared_size();
+        for partition in &self.objects {
+            match (&partition.kind, &partition.terms) {
+                (Some(TermKind::TypedLiteral(kind)), Terms::Integers(values))
+                    if kind == datatype =>
+                {
+                    let first = match bounds.start_bound() {
+                        Bound::Included(value) => values.partition_point(|x| x < value),
+                        Bound::Excluded(value) => values.partition_point(|x| x <= value),
+                        Bound::Unbounded => 0,
+                    };
+                    let last = match bounds.end_bound() {
+                        Bound::Included(value) => values.partition_point(|x| x <= value),
+                        Bound::Excluded(value) => values.partition_point(|x| x < value),
+                        Bound::Unbounded => values.len(),
+                    };
+                    return Some(start + first..start + last.max(first));
+                }
+                _ => start += partition.terms.len(),
+            }
+        }
+        None
+    }
+
+    /// Returns whether the terms are fetched from the store when needed,
+    /// instead of being fully loaded in memory.
+    pub fn is_lazy(&self) -> bool {
+        matches!(self.predicates, Terms::Lazy(_))
+    }
+
+    pub fn get_reference_system(&self) -> ReferenceSystem {
+        self.reference_system.to_owned()
+    }
+
+    pub fn get_subject_idx(&self, subject: &str) -> Option<usize> {
+        self.try_get_subject_idx(subject).ok().flatten()
+    }
+
+    /// Returns the index of a subject, if it is in the Dictionary.
+    ///
+    /// # Errors
+    /// Returns an error if the Dictionary is lazy and the terms cannot be
+    /// fetched from the store, which [`Dictionary::get_subject_idx`] cannot
+    /// tell apart from a missing subject.
+    pub fn try_get_subject_idx(&self, subject: &str) -> StorageResult<Option<usize>> {
+        Ok(locate(&self.subject_sections(), subject)?.map(|value| value + self.subjects_offset()))
+    }
+
+    pub fn get_subject_idx_unchecked(&self, subject: &str) -> usize {
+        self.get_subject_idx(subject).unwrap()
+    }
+
+    pub fn get_predicate_idx(&self, predicate: &str) -> Option<usize> {
+        self.try_get_predicate_idx(predicate).ok().flatten()
+    }
+
+    /// Returns the index of a predicate, if it is in the Dictionary.
+    ///
+    /// # Errors
+    /// Returns an error if the Dictionary is lazy and the terms cannot be
+    /// fetched from the store.
+    pub fn try_get_predicate_idx(&self, predicate: &str) -> StorageResult<Option<usize>> {
+        Ok(self
+            .predicates
+            .locate(predicate)?
+            .map(|value| value + self.predicates_offset()))
+    }
+
+    pub fn get_predicate_idx_unchecked(&self, predicate: &str) -> usize {
+        self.get_predicate_idx(predicate).unwrap()
+    }
+
+    pub fn get_object_idx(&self, object: &str) -> Option<usize> {
+        self.try_get_object_idx(object).ok().flatten()
+    }
+
+    /// Returns the index of an object, if it is in the Dictionary.
+    ///
+    /// # Errors
+    /// Returns an error if the Dictionary is lazy and the terms cannot be
+    /// fetched from the store.
+    pub fn try_get_object_idx(&self, object: &str) -> StorageResult<Option<usize>> {
+        Ok(locate(&self.object_sections(), object)?.map(|value| value + self.objects_offset()))
+    }
+
+    pub fn get_object_idx_unchecked(&self, object: &str) -> usize {
+        self.get_object_idx(object).unwrap()
+    }
+
+    /// Returns the index that the subject with the provided index has as an
+    /// object, if it is also an object. Using [`DictionaryFormat::FourSection`],
+    /// the shared terms have the same index in both roles, up to the shift of
+    /// the second term of the array; hence, subjects and objects are joined
+    /// without decoding them. Otherwise, the subject has to be decoded first.
+    pub fn subject_to_object_idx(&self, index: usize) -> Option<usize> {
+        match &self.shared {
+            Some(_) => index
+                .checked_sub(self.subjects_offset())
+                .filter(|&index| index < self.shared_size())
+                .map(|index| index + self.objects_offset()),
+            None => self.get_object_idx(&self.get_subject(index)?),
+        }
+    }
+
+    /// The counterpart of [`Dictionary::subject_to_object_idx`], which returns
+    /// the index that an object has as a subject, if it is also a subject.
+    pub fn object_to_subject_idx(&self, index: usize) -> Option<usize> {
+        match &self.shared {
+            Some(_) => index
+                .checked_sub(self.objects_offset())
+                .filter(|&index| index < self.shared_size())
+                .map(|index| index + self.subjects_offset()),
+            None => self.get_subject_idx(&self.get_object(index)?),
+        }
+    }
+
+    /// Returns the index of the partition in which the triples of a named
+    /// graph are stored, if there is such a graph
+    pub fn get_graph_idx(&self, graph: &str) -> Option<usize> {
+        self.graphs.as_ref()?.locator().run(graph)
+    }
+
+    pub fn get_graph(&self, index: usize) -> Option<String> {
+        match &self.graphs {
+            Some(graphs) if index < graphs.len() => {
+                String::from_utf8(graphs.decoder().run(index)).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterates over all the named graphs in the Dictionary, in ascending
+    /// order of their indices, as `(index, graph)` pairs.
+    pub fn iter_graphs(&self) -> impl Iterator<Item = (usize, String)> + '_ {
+        self.graphs.iter().flat_map(|graphs| {
+            graphs
+                .iter()
+                .map(|(index, graph)| (index, String::from_utf8(graph).unwrap_or_default()))
+        })
+    }
+
+    pub fn get_subject(&self, index: usize) -> Option<String> {
+        self.try_get_subject(index).ok().flatten()
+    }
+
+    /// Returns the subject with the provided index, if there is such.
+    ///
+    /// # Errors
+    /// Returns an error if the Dictionary is lazy and the terms cannot be
+    /// fetched from the store.
+    pub fn try_get_subject(&self, index: usize) -> StorageResult<Option<String>> {
+        self.try_decode_subjects([index]).next().unwrap_or(Ok(None))
+    }
+
+    pub fn get_subject_unchecked(&self, index: usize) -> String {
+        self.get_subject(index).unwrap()
+    }
+
+    pub fn get_predicate(&self, index: usize) -> Option<String> {
+        self.try_get_predicate(index).ok().flatten()
+    }
+
+    /// Returns the predicate with the provided index, if there is such.
+    ///
+    /// # Errors
+    /// Returns an error if the Dictionary is lazy and the terms cannot be
+    /// fetched from the store.
+    pub fn try_get_predicate(&self, index: usize) -> StorageResult<Option<String>> {
+        self.try_decode_predicates([index])
+            .next()
+            .unwrap_or(Ok(None))
+    }
+
+    pub fn get_predicate_unchecked(&self, index: usize) -> String {
+        self.get_predicate(index).unwrap()
+    }
+
+    pub fn get_object(&self, index: usize) -> Option<String> {
+        self.try_get_object(index).ok().flatten()
+    }
+
+    /// Returns the object with the provided index, if there is such.
+    ///
+    /// # Errors
+    /// Returns an error if the Dictionary is lazy and the terms cannot be
+    /// fetched from the store.
+    pub fn try_get_object(&self, index: usize) -> StorageResult<Option<String>> {
+        self.try_decode_objects([index]).next().unwrap_or(Ok(None))
+    }
+
+    pub fn get_object_unchecked(&self, index: usize) -> String {
+        self.get_object(index).unwrap()
+    }
+
+    pub fn get_object_kind(&self, index: usize) -> Option<TermKind> {
+        let sections = self.object_sections();
+        let (section, _) = find(&sections, index.checked_sub(self.objects_offset())?)?;
+        match sections[section].0 {
+            Some(kind) => Some(kind.to_owned()),
+            None => self.get_object(index).map(|object| TermKind::of(&object)),
+        }
+    }
+
+    /// Returns the lexical form of a literal object, without escaping it, next
+    /// to its kind; that is, its language tag or datatype
+    pub fn get_literal(&self, index: usize) -> Option<(String, TermKind)> {
+        let kind = self.get_object_kind(index).filter(TermKind::is_literal)?;
+        match parse_term(&self.get_object(index)?) {
+            Some(ParsedTerm::Literal(value, _)) => Some((value, kind)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an object whose datatype is an integer one, which
+    /// is read without decoding any string if the literals are kept as integers
+    pub fn get_integer(&self, index: usize) -> Option<i64> {
+        let sections = self.object_sections();
+        let (section, local) = find(&sections, index.checked_sub(self.objects_offset())?)?;
+        match sections[section] {
+            (_, Terms::Integers(values)) => values.get(local).copied(),
+            (Some(kind), _) if kind.is_integer() => {
+                let (value, _) = self.get_literal(index)?;
+                value.parse().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Decodes every provided index into its subject, reusing the same decoder
+    /// for all of them. Indices that are not in the Dictionary yield `None`.
+    pub fn decode_subjects<'a>(
+        &'a self,
+        indices: impl IntoIterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = Option<String>> + 'a {
+        self.try_decode_subjects(indices)
+            .map(|term| term.ok().flatten())
+    }
+
+    /// The counterpart of [`Dictionary::decode_subjects`] that returns an
+    /// error for the subjects that cannot be fetched from the store.
+    pub fn try_decode_subjects<'a>(
+        &'a self,
+        indices: impl IntoIterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = StorageResult<Option<String>>> + 'a {
+        decode_terms(self.subject_sections(), self.subjects_offset(), indices)
+    }
+
+    /// Decodes every provided index into its predicate, reusing the same
+    /// decoder for all of them. Indices that are not in the Dictionary yield
+    /// `None`.
+    pub fn decode_predicates<'a>(
+        &'a self,
+        indices: impl IntoIterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = Option<String>> + 'a {
+        self.try_decode_predicates(indices)
+            .map(|term| term.ok().flatten())
+    }
+
+    /// The counterpart of [`Dictionary::decode_predicates`] that returns an
+    /// error for the predicates that cannot be fetched from the store.
+    pub fn try_decode_predicates<'a>(
+        &'a self,
+        indices: impl IntoIterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = StorageResult<Option<String>>> + 'a {
+        decode_terms(self.predicate_sections(), self.predicates_offset(), indices)
+    }
+
+    /// Decodes every provided index into its object, reusing the same decoder
+    /// for all of them. Indices that are not in the Dictionary yield `None`.
+    pub fn decode_objects<'a>(
+        &'a self,
+        indices: impl IntoIterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = Option<String>> + 'a {
+        self.try_decode_objects(indices)
+            .map(|term| term.ok().flatten())
+    }
+
+    /// The counterpart of [`Dictionary::decode_objects`] that returns an
+    /// error for the objects that cannot be fetched from the store.
+    pub fn try_decode_objects<'a>(
+        &'a self,
+        indices: impl IntoIterator<Item = usize> + 'a,
+    ) -> impl Iterator<Item = StorageResult<Option<String>>> + 'a {
+        decode_terms(self.object_sections(), self.objects_offset(), indices)
+    }
+
+    /// Iterates over all the subjects in the Dictionary, in ascending order of
+    /// their indices, as `(index, subject)` pairs.
+    pub fn iter_subjects(&self) -> impl Iterator<Item = (usize, String)> + '_ {
+        iter_terms(self.subject_sections(), self.subjects_offset())
+    }
+
+    /// Iterates over all the predicates in the Dictionary, in ascending order
+    /// of their indices, as `(index, predicate)` pairs.
+    pub fn iter_predicates(&self) -> impl Iterator<Item = (usize, String)> + '_ {
+        iter_terms(self.predicate_sections(), self.predicates_offset())
+    }
+
+    /// Iterates over all the objects in the Dictionary, in ascending order of
+    /// their indices, as `(index, object)` pairs.
+    pub fn iter_objects(&self) -> impl Iterator<Item = (usize, String)> + '_ {
+        iter_terms(self.object_sections(), self.objects_offset())
+    }
+
+    // The shared terms go first, followed by the rest of the subjects, or by
+    // the partitions of the objects
+    fn subject_sections(&self) -> Vec<Section<'_>> {
+        self.shared
+            .iter()
+            .chain([&self.subjects])
+            .map(|terms| (None, terms))
+            .collect()
+    }
+
+    fn predicate_sections(&self) -> Vec<Section<'_>> {
+        vec![(None, &self.predicates)]
+    }
+
+    fn object_sections(&self) -> Vec<Section<'_>> {
+        self.shared
+            .iter()
+            .map(|terms| (None, terms))
+            .chain(
+                self.objects
+                    .iter()
+                    .map(|partition| (partition.kind.as_ref(), &partition.terms)),
+            )
+            .collect()
+    }
+
+    // The term that is stored in the second position of the array is shifted
+    // by one, as 0 is the fill value of the array; that is, it marks that no
+    // triple is stored in a certain cell
+    fn subjects_offset(&self) -> usize {
+        match self.reference_system {
+            ReferenceSystem::PSO | ReferenceSystem::OSP => 1,
+            _ => 0,
+        }
+    }
+
+    fn predicates_offset(&self) -> usize {
+        match self.reference_system {
+            ReferenceSystem::SPO | ReferenceSystem::OPS => 1,
+            _ => 0,
+        }
+    }
+
+    fn objects_offset(&self) -> usize {
+        match self.reference_system {
+            ReferenceSystem::SOP | ReferenceSystem::POS => 1,
+            _ => 0,
+        }
+    }
+}
+
+fn sections_len(sections: &[Section]) -> usize {
+    sections.iter().map(|(_, terms)| terms.len()).sum()
+}
+
+/// The index at which every section starts
+fn starts(sections: &[Section]) -> Vec<usize> {
+    sections
+        .iter()
+        .scan(0, |start, (_, terms)| {
+            let section_start = *start;
+            *start += terms.len();
+            Some(section_start)
+        })
+        .collect()
+}
+
+/// Returns the section in which an index is, next to its index within it
+fn find(sections: &[Section], index: usize) -> Option<(usize, usize)> {
+    let starts = starts(sections);
+    // The last of the sections starting at or before the index, which skips
+    // the empty ones that start at the same index
+    let section = starts
+        .partition_point(|&start| start <= index)
+        .checked_sub(1)?;
+    let local = index - starts[section];
+    (local < sections[section].1.len()).then_some((section, local))
+}
+
+fn locate(sections: &[Section], term: &str) -> StorageResult<Option<usize>> {
+    let kind = TermKind::of(term);
+    let mut start = 0;
+    for (section_kind, terms) in sections {
+        let local = match section_kind {
+            Some(section_kind) if **section_kind == kind => Some(kind.to_local(term)),
+            Some(_) => None,
+            None => Some(term),
+        };
+        if let Some(local) = local {
+            if let Some(index) = terms.locate(local)? {
+                return Ok(Some(start + index));
+            }
+        }
+        start += terms.len();
+    }
+    Ok(None)
+}
+
+fn decode_terms<'a>(
+    sections: Vec<Section<'a>>,
+    offset: usize,
+    indices: impl IntoIterator<Item = usize> + 'a,
+) -> impl Iterator<Item = StorageResult<Option<String>>> + 'a {
+    let mut decoders = sections
+        .iter()
+        .map(|(_, terms)| terms.decoder())
+        .collect::<Vec<_>>();
+    indices.into_iter().map(move |index| {
+        let (section, local) = match index
+            .checked_sub(offset)
+            .and_then(|index| find(&sections, index))
+        {
+            Some(position) => position,
+            None => return Ok(None),
+        };
+        let (kind, terms) = sections[section];
+        let term = terms.decode(decoders[section].as_mut(), local)?;
+        Ok(term.map(|term| match kind {
+            Some(kind) => kind.to_term(term),
+            None => term,
+        }))
+    })
+}
+
+fn iter_terms<'a>(
+    sections: Vec<Section<'a>>,
+    offset: usize,
+) -> impl Iterator<Item = (usize, String)> + 'a {
+    let starts = starts(&sections);
+    sections
+        .into_iter()
+        .zip(starts)
+        .flat_map(move |((kind, terms), start)| {
+            iter_section(terms, offset + start).map(move |(index, term)| match kind {
+                Some(kind) => (index, kind.to_term(term)),
+                None => (index, term),
+            })
+        })
+}
+
+fn iter_section(terms: &Terms, offset: usize) -> Box<dyn Iterator<Item = (usize, String)> + '_> {
+    match terms {
+        Terms::Loaded(set) => Box::new(set.iter().map(move |(index, term)| {
+            (index + offset, String::from_utf8(term).unwrap_or_default())
+        })),
+        Terms::Lazy(set) => Box::new(
+            (0..set.len()).map_while(move |index| Some((index + offset, set.decode(index).ok()??))),
+        ),
+        Terms::Integers(values) => Box::new(
+            values
+                .iter()
+                .enumerate()
+                .map(move |(index, value)| (index + offset, format!("\"{}\"", value))),
+        ),
+        Terms::Empty => Box::new(std::iter::empty()),
+    }
+}
+
+impl TermsView<'_> {
+    pub fn len(&self) -> usize {
+        sections_len(&self.sections)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The term at a position, if there is such
+    pub fn get(&self, position: usize) -> Option<String> {
+        decode_terms(self.sections.to_owned(), 0, [position])
+            .next()
+            .and_then(|term| term.ok().flatten())
+    }
+
+    /// The position of a term, if it is in the view
+    pub fn locate(&self, term: &str) -> Option<usize> {
+        locate(&self.sections, term).ok().flatten()
+    }
+
+    /// Iterates over the terms as `(position, term)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (usize, String)> + '_ {
+        iter_terms(self.sections.to_owned(), 0)
+    }
+}
+
+fn builder() -> Result<Builder, ParserError> {
+    match Builder::new(DEFAULT_BUCKET_SIZE) {
+        Ok(builder) => Ok(builder),
+        Err(err) => Err(ParserError::Dictionary(err.to_string())),
+    }
+}
+
+/// Adds a term to a front-coded set, which must be greater than the last one
+fn add(builder: &mut Builder, term: &str) -> Result<(), ParserError> {
+    match builder.add(term.as_bytes()) {
+        Ok(()) => Ok(()),
+        Err(err) => Err(ParserError::Dictionary(err.to_string())),
+    }
+}
+
+// Front-coded sets cannot be empty; as such, the builder of a section is
+// only created once its first term is added
+fn add_to_section(section: &mut Option<Builder>, term: &str) -> Result<(), ParserError> {
+    match section {
+        Some(builder) => add(builder, term),
+        None => add(section.insert(builder()?), term),
+    }
+}
+
+fn to_terms(section: Option<Builder>) -> Terms {
+    match section {
+        Some(builder) => Terms::Loaded(builder.finish()),
+        None => Terms::Empty,
+    }
+}
+
+impl From<Set> for Terms {
+    fn from(set: Set) -> Self {
+        Terms::Loaded(set)
+    }
+}
+
+impl From<LazySet> for Terms {
+    fn from(set: LazySet) -> Self {
+        Terms::Lazy(set)
+    }
+}
+
+impl Terms {
+    fn len(&self) -> usize {
+        match self {
+            Terms::Loaded(set) => set.len(),
+            Terms::Lazy(set) => set.len(),
+            Terms::Integers(values) => values.len(),
+            Terms::Empty => 0,
+        }
+    }
+
+    fn locate(&self, term: &str) -> StorageResult<Option<usize>> {
+        match self {
+            Terms::Loaded(set) => Ok(set.locator().run(term)),
+            Terms::Lazy(set) => set.locate(term),
+            Terms::Integers(values) => {
+                Ok(parse_integer(term).and_then(|value| values.binary_search(&value).ok()))
+            }
+            Terms::Empty => Ok(None),
+        }
+    }
+
+    fn decoder(&self) -> Option<Decoder<'_>> {
+        match self {
+            Terms::Loaded(set) => Some(set.decoder()),
+            _ => None,
+        }
+    }
+
+    fn decode(&self, decoder: Option<&mut Decoder>, index: usize) -> StorageResult<Option<String>> {
+        // The decoder panics if the index is out of bounds; as such, we have to
+        // check it beforehand for us to return None in that case
+        if index >= self.len() {
+            return Ok(None);
+        }
+        match (self, decoder) {
+            (_, Some(decoder)) => Ok(String::from_utf8(decoder.run(index)).ok()),
+            (Terms::Lazy(set), None) => set.decode(index),
+            (Terms::Integers(values), None) => Ok(Some(format!("\"{}\"", values[index]))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Finds the first term for which the predicate is false, as in
+    /// [`slice::partition_point`], decoding as few terms as possible
+    fn partition_point(&self, predicate: impl Fn(&str) -> bool) -> usize {
+        let mut decoder = self.decoder();
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.decode(decoder.as_mut(), middle) {
+                Ok(Some(term)) if predicate(&term) => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+        low
+    }
+
+    /// The terms as a front-coded set, which is `None` for the empty sections
+    /// and for the integer literals, as they are not stored as such.
+    ///
+    /// # Errors
+    /// If the terms are lazily loaded, they have to be fetched from the store,
+    /// returning an error if they cannot be retrieved.
+    pub(crate) fn to_set(&self) -> StorageResult<Option<Set>> {
+        match self {
+            Terms::Loaded(set) => Ok(Some(set.to_owned())),
+            Terms::Lazy(set) => Ok(Some(set.to_set()?)),
+            Terms::Integers(_) | Terms::Empty => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use fcsd::builder::Builder;
+use serde_json::Map;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use crate::error::ParserError;
+
+use super::add;
+use super::builder;
+use super::Terms;
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+// The datatypes whose values fit into a signed 64-bit integer, as long as
+// they are written in their canonical form
+const INTEGER_DATATYPES: [&str; 12] = [
+    "integer",
+    "long",
+    "int",
+    "short",
+    "byte",
+    "nonNegativeInteger",
+    "positiveInteger",
+    "nonPositiveInteger",
+    "negativeInteger",
+    "unsignedInt",
+    "unsignedShort",
+    "unsignedByte",
+];
+
+/// The kind of an object, which tells the partition of the objects in which it
+/// is stored. Literals are partitioned by their language tag or datatype; as
+/// such, their partitions only keep their quoted lexical forms. The kinds are
+/// sorted in the same order as the partitions; that is, IRIs and blank nodes
+/// go first, followed by the literals.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TermKind {
+    Iri,
+    BlankNode,
+    Literal,
+    LanguageLiteral(String),
+    TypedLiteral(String),
+}
+
+impl TermKind {
+    /// Returns the kind of a term written as in N-Triples. Terms that are
+    /// neither blank nodes nor literals are considered IRIs.
+    pub fn of(term: &str) -> TermKind {
+        let suffix = match term.rfind('"') {
+            Some(end) if end > 0 && term.starts_with('"') => &term[end + 1..],
+            _ => match term.starts_with("_:") {
+                true => return TermKind::BlankNode,
+                false => return TermKind::Iri,
+            },
+        };
+        match (suffix.strip_prefix('@'), suffix.strip_prefix("^^<")) {
+            (Some(language), _) => TermKind::LanguageLiteral(language.to_string()),
+            (_, Some(datatype)) => TermKind::TypedLiteral(datatype.trim_end_matches('>').into()),
+            _ => TermKind::Literal,
+        }
+    }
+
+    pub fn is_literal(&self) -> bool {
+        !matches!(self, TermKind::Iri | TermKind::BlankNode)
+    }
+
+    /// Whether the literals of this kind are integers that may be kept as such
+    pub(crate) fn is_integer(&self) -> bool {
+        match self {
+            TermKind::TypedLiteral(datatype) => datatype
+                .strip_prefix(XSD)
+                .is_some_and(|name| INTEGER_DATATYPES.contains(&name)),
+            _ => false,
+        }
+    }
+
+    /// The part of a term of this kind that is stored in its partition
+    pub(crate) fn to_local<'a>(&self, term: &'a str) -> &'a str {
+        match (self.is_literal(), term.rfind('"')) {
+            (true, Some(end)) => &term[..=end],
+            _ => term,
+        }
+    }
+
+    /// The inverse of [`TermKind::to_local`]
+    pub(crate) fn to_term(&self, local: String) -> String {
+        match self {
+            TermKind::LanguageLiteral(language) => format!("{}@{}", local, language),
+            TermKind::TypedLiteral(datatype) => format!("{}^^<{}>", local, datatype),
+            _ => local,
+        }
+    }
+}
+
+/// A partition of the objects, which holds the ones of a certain kind. The
+/// integers are kept in ascending order of their values instead of their
+/// strings; hence, a range of values is a range of indices. Datasets that were
+/// serialized before the objects were partitioned hold all of them in a single
+/// partition of no kind, whose terms are stored as they are.
+#[derive(Clone)]
+pub(crate) struct Partition {
+    pub(crate) kind: Option<TermKind>,
+    pub(crate) terms: Terms,
+}
+
+impl Partition {
+    /// Describes the partition, so that it can be read back from the store
+    pub(crate) fn descriptor(&self) -> Value {
+        let mut descriptor = Map::new();
+        let (kind, value) = match &self.kind {
+            None => ("any", None),
+            Some(TermKind::Iri) => ("iri", None),
+            Some(TermKind::BlankNode) => ("blank_node", None),
+            Some(TermKind::Literal) => ("literal", None),
+            Some(TermKind::LanguageLiteral(language)) => ("language", Some(language)),
+            Some(TermKind::TypedLiteral(datatype)) => ("datatype", Some(datatype)),
+        };
+        descriptor.insert("kind".into(), kind.into());
+        if let Some(value) = value {
+            descriptor.insert("value".into(), value.as_str().into());
+        }
+        descriptor.insert(
+            "integers".into(),
+            matches!(self.terms, Terms::Integers(_)).into(),
+        );
+        descriptor.into()
+    }
+
+    /// Reads the kind of a partition from its descriptor, next to whether its
+    /// terms are stored as integers
+    pub(crate) fn from_descriptor(descriptor: &Value) -> Option<(Option<TermKind>, bool)> {
+        let value = || descriptor.get("value")?.as_str().map(String::from);
+        let kind = match descriptor.get("kind")?.as_str()? {
+            "any" => None,
+            "iri" => Some(TermKind::Iri),
+            "blank_node" => Some(TermKind::BlankNode),
+            "literal" => Some(TermKind::Literal),
+            "language" => Some(TermKind::LanguageLiteral(value()?)),
+            "datatype" => Some(TermKind::TypedLiteral(value()?)),
+            _ => return None,
+        };
+        Some((kind, descriptor.get("integers")?.as_bool()?))
+    }
+}
+
+/// Compares two terms in the order in which the partitions hold them; that
+/// is, by their kind, and then by the part of them that is stored. IRIs and
+/// blank nodes are thus compared as strings, while the literals of a kind are
+/// compared by their quoted lexical forms.
+pub(crate) fn cmp_terms(a: &str, b: &str) -> Ordering {
+    sort_key(a).cmp(&sort_key(b)).then_with(|| a.cmp(b))
+}
+
+// The same as `TermKind::of` followed by `TermKind::to_local`, but without
+// allocating the language tag nor the datatype, as it is called for every
+// comparison while sorting the objects
+fn sort_key(term: &str) -> (u8, &str, &str) {
+    let end = match term.rfind('"') {
+        Some(end) if end > 0 && term.starts_with('"') => end,
+        _ => return (term.starts_with("_:") as u8, "", term),
+    };
+    let (local, suffix) = term.split_at(end + 1);
+    match (suffix.strip_prefix('@'), suffix.strip_prefix("^^<")) {
+        (Some(language), _) => (3, language, local),
+        (_, Some(datatype)) => (4, datatype.trim_end_matches('>'), local),
+        _ => (2, "", local),
+    }
+}
+
+/// Splits the objects into partitions by their kind as they are pushed, which
+/// must be in the order of [`cmp_terms`]; hence, every partition is built
+/// right after the previous one, and the objects are never kept uncompressed.
+/// The literals of an integer datatype are stored as integers if all of them
+/// are written in their canonical form, as otherwise they could not be
+/// written back as they were.
+#[derive(Default)]
+pub(crate) struct Partitioner {
+    partitions: Vec<Partition>,
+    current: Option<(TermKind, PartitionBuilder)>,
+}
+
+enum PartitionBuilder {
+    Integers(Vec<i64>),
+    Strings(Builder),
+}
+
+impl Partitioner {
+    pub(crate) fn push(&mut self, object: &str) -> Result<(), ParserError> {
+        let kind = TermKind::of(object);
+        let local = kind.to_local(object);
+        match &mut self.current {
+            Some((current, builder)) if *current == kind => builder.push(local),
+            _ => {
+                self.flush();
+                if let Some(Partition {
+                    kind: Some(last), ..
+                }) = self.partitions.last()
+                {
+                    if *last > kind {
+                        return Err(ParserError::Dictionary(
+                            "The objects are not sorted by their kind".to_string(),
+                        ));
+                    }
+                }
+                let mut builder = PartitionBuilder::new(&kind)?;
+                builder.push(local)?;
+                self.current = Some((kind, builder));
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<Partition> {
+        self.flush();
+        self.partitions
+    }
+
+    fn flush(&mut self) {
+        if let Some((kind, builder)) = self.current.take() {
+            self.partitions.push(Partition {
+                kind: Some(kind),
+                terms: builder.finish(),
+            });
+        }
+    }
+}
+
+impl PartitionBuilder {
+    fn new(kind: &TermKind) -> Result<Self, ParserError> {
+        match kind.is_integer() {
+            true => Ok(PartitionBuilder::Integers(Vec::new())),
+            false => Ok(PartitionBuilder::Strings(builder()?)),
+        }
+    }
+
+    fn push(&mut self, local: &str) -> Result<(), ParserError> {
+        match self {
+            PartitionBuilder::Integers(values) => match parse_integer(local) {
+                Some(value) => {
+                    values.push(value);
+                    Ok(())
+                }
+                // The integers are pushed in the same order as their strings;
+                // as such, they can be turned back into them before this one
+                None => {
+                    let mut strings = builder()?;
+                    for value in values.iter() {
+                        add(&mut strings, &format!("\"{}\"", value))?;
+                    }
+                    add(&mut strings, local)?;
+                    *self = PartitionBuilder::Strings(strings);
+                    Ok(())
+                }
+            },
+            PartitionBuilder::Strings(strings) => add(strings, local),
+        }
+    }
+
+    fn finish(self) -> Terms {
+        match self {
+            PartitionBuilder::Integers(mut values) => {
+                values.sort_unstable();
+                Terms::Integers(Arc::new(values))
+            }
+            PartitionBuilder::Strings(strings) => Terms::Loaded(strings.finish()),
+        }
+    }
+}
+
+/// Parses the quoted lexical form of an integer, which must be canonical; that
+/// is, without leading zeros nor a plus sign
+pub(crate) fn parse_integer(local: &str) -> Option<i64> {
+    let lexical = local.strip_prefix('"')?.strip_suffix('"')?;
+    let value = lexical.parse::<i64>().ok()?;
+    (value.to_string() == lexical).then_some(value)
+}
@@ -29,4 +29,38 @@ impl EngineStrategy<ZarrArray> for ZarrArray {
         let matrix = matrix.to_csc();
         Ok(self * &matrix)
     }
+
+    fn get_first_and_second_terms(&self, first: usize, second: usize) -> EngineResult<ZarrArray> {
+        let row = self.get_first_term(first)?;
+        let mut matrix = TriMat::new((self.rows(), self.cols()));
+        row.iter().for_each(|(&e, (row, col))| {
+            if e == second {
+                matrix.add_triplet(row, col, e);
+            }
+        });
+        Ok(matrix.to_csc())
+    }
+
+    fn get_first_and_third_terms(&self, first: usize, third: usize) -> EngineResult<ZarrArray> {
+        let mut matrix = TriMat::new((self.rows(), self.cols()));
+        if let Some(&e) = self.get(first, third) {
+            matrix.add_triplet(first, third, e);
+        }
+        Ok(matrix.to_csc())
+    }
+
+    fn get_second_and_third_terms(&self, second: usize, third: usize) -> EngineResult<ZarrArray> {
+        let column = self.get_third_term(third)?;
+        let mut matrix = TriMat::new((self.rows(), self.cols()));
+        column.iter().for_each(|(&e, (row, col))| {
+            if e == second {
+                matrix.add_triplet(row, col, e);
+            }
+        });
+        Ok(matrix.to_csc())
+    }
+
+    fn contains(&self, first: usize, second: usize, third: usize) -> EngineResult<bool> {
+        Ok(self.get(first, third).is_some_and(|&e| e == second))
+    }
 }
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use zarrs::array::Array;
+use zarrs::storage::AsyncReadableStorageTraits;
+
+use super::cell;
+use super::column;
+use super::is_stored;
+use super::number_of_shards;
+use super::retain;
+use super::row_in_shard;
+use super::AsyncEngineStrategy;
+use super::EngineResult;
+
+#[async_trait]
+impl<T: AsyncReadableStorageTraits + 'static + ?Sized> AsyncEngineStrategy<Vec<u32>> for Array<T> {
+    async fn get_first_term(&self, index: usize) -> EngineResult<Vec<u32>> {
+        let (shard_index, cells) = row_in_shard(self, index);
+        let shard = self
+            .async_retrieve_chunk_elements::<u32>(&shard_index)
+            .await?;
+        Ok(shard[cells].to_vec())
+    }
+
+    async fn get_second_term(&self, index: usize) -> EngineResult<Vec<u32>> {
+        // All the shards are requested at once, while they are concatenated in
+        // the same order as they are stored in the array
+        let shards = try_join_all(
+            (0..number_of_shards(self)?)
+                .map(|i| async move { self.async_retrieve_chunk_elements::<u32>(&[i, 0]).await }),
+        )
+        .await?;
+        let mut ans = shards.concat();
+        retain(&mut ans, index);
+        Ok(ans)
+    }
+
+    async fn get_third_term(&self, index: usize) -> EngineResult<Vec<u32>> {
+        Ok(self
+            .async_retrieve_array_subset_elements::<u32>(&column(self, index))
+            .await?)
+    }
+
+    async fn get_first_and_second_terms(
+        &self,
+        first: usize,
+        second: usize,
+    ) -> EngineResult<Vec<u32>> {
+        let mut row = self.get_first_term(first).await?;
+        retain(&mut row, second);
+        Ok(row)
+    }
+
+    async fn get_first_and_third_terms(
+        &self,
+        first: usize,
+        third: usize,
+    ) -> EngineResult<Vec<u32>> {
+        Ok(self
+            .async_retrieve_array_subset_elements::<u32>(&cell(first, third))
+            .await?)
+    }
+
+    async fn get_second_and_third_terms(
+        &self,
+        second: usize,
+        third: usize,
+    ) -> EngineResult<Vec<u32>> {
+        let mut column = self.get_third_term(third).await?;
+        retain(&mut column, second);
+        Ok(column)
+    }
+
+    async fn contains(&self, first: usize, second: usize, third: usize) -> EngineResult<bool> {
+        Ok(is_stored(
+            &self.get_first_and_third_terms(first, third).await?,
+            second,
+        ))
+    }
+}
@@ -1,48 +1,55 @@
 use zarrs::array::Array;
-use zarrs::array_subset::ArraySubset;
 use zarrs::storage::ReadableStorageTraits;
 
-use crate::error::EngineError;
-use crate::utils::columns_per_shard;
-use crate::utils::rows_per_shard;
-
+use super::cell;
+use super::column;
+use super::is_stored;
+use super::number_of_shards;
+use super::retain;
+use super::row_in_shard;
 use super::EngineResult;
 use super::EngineStrategy;
 
 impl<T: ReadableStorageTraits + 'static + ?Sized> EngineStrategy<Vec<u32>> for Array<T> {
     fn get_first_term(&self, index: usize) -> EngineResult<Vec<u32>> {
-        let shard_index = index as u64 / rows_per_shard(self);
-        let shard = self.retrieve_chunk_elements(&[shard_index, 0])?;
-        let chunk_index = index as u64 % rows_per_shard(self);
-        let start = (chunk_index * columns_per_shard(self)) as usize;
-        let end = start + columns_per_shard(self) as usize;
-        let chunk: &[u32] = &shard[start..end];
-        Ok(chunk.to_vec())
+        let (shard_index, cells) = row_in_shard(self, index);
+        let shard = self.retrieve_chunk_elements::<u32>(&shard_index)?;
+        Ok(shard[cells].to_vec())
     }
 
     fn get_second_term(&self, index: usize) -> EngineResult<Vec<u32>> {
         let mut ans = Vec::new();
-        let number_of_shards = match self.chunk_grid_shape() {
-            Some(chunk_grid) => chunk_grid[0],
-            None => return Err(EngineError::Operation),
-        };
-        for i in 0..number_of_shards {
-            let mut shard = self.retrieve_chunk_elements::<u32>(&[i, 0])?;
-            shard.iter_mut().for_each(|e| {
-                if *e != index as u32 {
-                    *e = 0
-                }
-            });
-            ans.append(&mut shard);
+        for i in 0..number_of_shards(self)? {
+            ans.append(&mut self.retrieve_chunk_elements::<u32>(&[i, 0])?);
         }
+        retain(&mut ans, index);
         Ok(ans)
     }
 
     fn get_third_term(&self, index: usize) -> EngineResult<Vec<u32>> {
-        let objects = self.shape()[0];
-        let col = index as u64;
-        let shape = ArraySubset::new_with_ranges(&[0..objects, col..col + 1]);
-        let array_subset = self.retrieve_array_subset_elements::<u32>(&shape)?;
-        Ok(array_subset)
+        Ok(self.retrieve_array_subset_elements::<u32>(&column(self, index))?)
+    }
+
+    fn get_first_and_second_terms(&self, first: usize, second: usize) -> EngineResult<Vec<u32>> {
+        let mut row = self.get_first_term(first)?;
+        retain(&mut row, second);
+        Ok(row)
+    }
+
+    fn get_first_and_third_terms(&self, first: usize, third: usize) -> EngineResult<Vec<u32>> {
+        Ok(self.retrieve_array_subset_elements::<u32>(&cell(first, third))?)
+    }
+
+    fn get_second_and_third_terms(&self, second: usize, third: usize) -> EngineResult<Vec<u32>> {
+        let mut column = self.get_third_term(third)?;
+        retain(&mut column, second);
+        Ok(column)
+    }
+
+    fn contains(&self, first: usize, second: usize, third: usize) -> EngineResult<bool> {
+        Ok(is_stored(
+            &self.get_first_and_third_terms(first, third)?,
+            second,
+        ))
     }
 }
@@ -1,6 +1,14 @@
+use async_trait::async_trait;
+use std::ops::Range;
+use zarrs::array::Array;
+use zarrs::array_subset::ArraySubset;
+
 use crate::error::EngineError;
+use crate::utils::columns_per_shard;
+use crate::utils::rows_per_shard;
 
 pub mod array;
+pub mod async_chunk;
 pub mod chunk;
 
 pub(crate) type EngineResult<T> = Result<T, EngineError>;
@@ -9,4 +17,117 @@ pub(crate) trait EngineStrategy<T> {
     fn get_first_term(&self, index: usize) -> EngineResult<T>;
     fn get_second_term(&self, index: usize) -> EngineResult<T>;
     fn get_third_term(&self, index: usize) -> EngineResult<T>;
+    fn get_first_and_second_terms(&self, first: usize, second: usize) -> EngineResult<T>;
+    fn get_first_and_third_terms(&self, first: usize, third: usize) -> EngineResult<T>;
+    fn get_second_and_third_terms(&self, second: usize, third: usize) -> EngineResult<T>;
+    fn contains(&self, first: usize, second: usize, third: usize) -> EngineResult<bool>;
+}
+
+/// The asynchronous counterpart of [`EngineStrategy`], for those engines whose
+/// data is retrieved from an asynchronous store.
+#[async_trait]
+pub(crate) trait AsyncEngineStrategy<T> {
+    async fn get_first_term(&self, index: usize) -> EngineResult<T>;
+    async fn get_second_term(&self, index: usize) -> EngineResult<T>;
+    async fn get_third_term(&self, index: usize) -> EngineResult<T>;
+    async fn get_first_and_second_terms(&self, first: usize, second: usize) -> EngineResult<T>;
+    async fn get_first_and_third_terms(&self, first: usize, third: usize) -> EngineResult<T>;
+    async fn get_second_and_third_terms(&self, second: usize, third: usize) -> EngineResult<T>;
+    async fn contains(&self, first: usize, second: usize, third: usize) -> EngineResult<bool>;
+}
+
+/// A call to an engine, whose terms are given in the positions in which they
+/// are stored; that is, the reference system has already been resolved. Hence,
+/// both kinds of engines answer a pattern the same way.
+pub(crate) enum Query {
+    First(usize),
+    Second(usize),
+    Third(usize),
+    FirstAndSecond(usize, usize),
+    FirstAndThird(usize, usize),
+    SecondAndThird(usize, usize),
+}
+
+impl Query {
+    pub(crate) fn run<T, E: EngineStrategy<T> + ?Sized>(self, engine: &E) -> EngineResult<T> {
+        match self {
+            Query::First(index) => engine.get_first_term(index),
+            Query::Second(index) => engine.get_second_term(index),
+            Query::Third(index) => engine.get_third_term(index),
+            Query::FirstAndSecond(first, second) => {
+                engine.get_first_and_second_terms(first, second)
+            }
+            Query::FirstAndThird(first, third) => engine.get_first_and_third_terms(first, third),
+            Query::SecondAndThird(second, third) => {
+                engine.get_second_and_third_terms(second, third)
+            }
+        }
+    }
+
+    pub(crate) async fn async_run<T, E: AsyncEngineStrategy<T> + Sync + ?Sized>(
+        self,
+        engine: &E,
+    ) -> EngineResult<T> {
+        match self {
+            Query::First(index) => engine.get_first_term(index).await,
+            Query::Second(index) => engine.get_second_term(index).await,
+            Query::Third(index) => engine.get_third_term(index).await,
+            Query::FirstAndSecond(first, second) => {
+                engine.get_first_and_second_terms(first, second).await
+            }
+            Query::FirstAndThird(first, third) => {
+                engine.get_first_and_third_terms(first, third).await
+            }
+            Query::SecondAndThird(second, third) => {
+                engine.get_second_and_third_terms(second, third).await
+            }
+        }
+    }
+}
+
+// The following helpers compute where the chunk engines read from, and how
+// they filter what they have read, so that only the retrieval differs between
+// the synchronous engine and the asynchronous one
+
+/// The shard in which a row is stored, next to the cells it takes within it
+fn row_in_shard<T: ?Sized>(array: &Array<T>, index: usize) -> ([u64; 2], Range<usize>) {
+    let shard_index = index as u64 / rows_per_shard(array);
+    let chunk_index = index as u64 % rows_per_shard(array);
+    let start = (chunk_index * columns_per_shard(array)) as usize;
+    let end = start + columns_per_shard(array) as usize;
+    ([shard_index, 0], start..end)
+}
+
+/// The number of shards in which the rows of the array are split
+fn number_of_shards<T: ?Sized>(array: &Array<T>) -> EngineResult<u64> {
+    match array.chunk_grid_shape() {
+        Some(chunk_grid) => Ok(chunk_grid[0]),
+        None => Err(EngineError::Operation),
+    }
+}
+
+fn column<T: ?Sized>(array: &Array<T>, index: usize) -> ArraySubset {
+    let (rows, col) = (array.shape()[0], index as u64);
+    ArraySubset::new_with_ranges(&[0..rows, col..col + 1])
+}
+
+// Only one cell is requested; that is, the one storing the second term that
+// joins both of the provided terms, so that we avoid fetching the whole row,
+// or column, from the store
+fn cell(first: usize, third: usize) -> ArraySubset {
+    let (row, col) = (first as u64, third as u64);
+    ArraySubset::new_with_ranges(&[row..row + 1, col..col + 1])
+}
+
+/// Empties the cells that do not store the provided second term
+fn retain(cells: &mut [u32], second: usize) {
+    cells.iter_mut().for_each(|e| {
+        if *e != second as u32 {
+            *e = 0
+        }
+    });
+}
+
+fn is_stored(cell: &[u32], second: usize) -> bool {
+    cell.first().is_some_and(|&e| e != 0 && e == second as u32)
 }
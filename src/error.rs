@@ -1,3 +1,4 @@
+use rayon::ThreadPoolBuildError;
 use std::convert::Infallible;
 use thiserror::Error;
 use zarrs::array::codec::bytes_to_bytes::gzip::GzipCompressionLevelError;
@@ -11,6 +12,9 @@ use zarrs::group::GroupCreateError;
 use zarrs::storage::store::FilesystemStoreCreateError;
 use zarrs::storage::store::HTTPStoreCreateError;
 use zarrs::storage::StorageError;
+use zarrs::storage::StoreKeyError;
+
+use crate::storage::params::RdfFormat;
 
 #[derive(Error, Debug)]
 pub enum RemoteHDTError {
@@ -30,6 +34,8 @@ pub enum RemoteHDTError {
     Array(#[from] ArrayError),
     #[error(transparent)]
     HTTPCreate(#[from] HTTPStoreCreateError),
+    #[error(transparent)]
+    StoreKey(#[from] StoreKeyError),
     #[error("The Path already exists, please provide an empty path")]
     PathExists,
     #[error("The Path does not exist, please provide another path")]
@@ -40,12 +46,10 @@ pub enum RemoteHDTError {
     EmptyGraph,
     #[error(transparent)]
     Ops(#[from] OpsError),
-    #[error("The subjects has not been serialized properly")]
-    SubjectsNotInJSON,
-    #[error("The predicates has not been serialized properly")]
-    PredicatesNotInJSON,
-    #[error("The objects has not been serialized properly")]
-    ObjectsNotInJSON,
+    #[error("The Dictionary could not be serialized into the store")]
+    DictionarySerialization,
+    #[error("The Dictionary has not been serialized properly")]
+    DictionaryDeserialization,
     #[error("The Reference System has not been serialized properly")]
     ReferenceSystemNotInJSON,
     #[error("Error serializing the triples of the Graph")]
@@ -54,10 +58,28 @@ pub enum RemoteHDTError {
     OsPathToString,
     #[error("The provided backend is read-only")]
     ReadOnlyBackend,
-    #[error("Error while parsing the RDF graph")]
-    RdfParse,
+    #[error("The provided backend can only be accessed asynchronously")]
+    AsyncBackend,
+    #[error("The provided backend can only be accessed synchronously")]
+    SyncBackend,
+    #[error("The blank nodes that are not read from a file can only be relabeled if a scope is provided")]
+    MissingBlankNodeScope,
+    #[error("Error while parsing the RDF graph: {0}")]
+    RdfParse(#[from] ParserError),
     #[error(transparent)]
     NonZero(#[from] NonZeroError),
+    #[error(transparent)]
+    ThreadPoolBuild(#[from] ThreadPoolBuildError),
+    #[error(transparent)]
+    ObjectStore(#[from] object_store::Error),
+    #[error(
+        "The dataset cannot be exported into {0:?}; only N-Triples, Turtle and HDT are supported"
+    )]
+    NotSupportedExport(RdfFormat),
+    #[error("The term `{0}` could not be exported")]
+    TermNotExported(String),
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
 }
 
 #[derive(Error, Debug)]
@@ -74,16 +96,49 @@ pub enum EngineError {
     Codec(#[from] CodecError),
 }
 
+const SUPPORTED_FORMATS: &str = "the supported formats are N-Triples (nt), Turtle (ttl, n3), \
+RDF/XML (rdf, owl, xml), N-Quads (nq), TriG (trig), JSON-LD (jsonld, json) and HDT (hdt)";
+
 #[derive(Error, Debug)]
 pub enum ParserError {
     #[error("Could not parse the Dicitonary: `{0}`")]
     Dictionary(String),
     #[error("Could not parse the Graph: `{0}`")]
     Graph(String),
-    #[error("Format {0} not supported")]
+    #[error("Format {0} not supported; {}", SUPPORTED_FORMATS)]
     NotSupportedFormat(String),
-    #[error("No format provided")]
+    #[error("The compressed RDF dump could not be decompressed")]
+    Decompression,
+    #[error(
+        "The format could be guessed neither from the path nor from the content; {}",
+        SUPPORTED_FORMATS
+    )]
     NoFormatProvided,
+    #[error("Could not read the HDT file: {0}")]
+    Hdt(String),
+    #[error("The RDF dump does not contain any valid triple")]
+    EmptyGraph,
+    #[error(transparent)]
+    Syntax(#[from] SyntaxError),
+}
+
+/// An invalid statement found in the RDF dump. Both the line and the column,
+/// which is measured in bytes, start from 1; they are 0 if the position of the
+/// error is unknown.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Syntax error at line {line}, column {column}: {message}")]
+pub struct SyntaxError {
+    pub line: u64,
+    pub column: u64,
+    pub message: String,
+}
+
+/// The statements that were skipped while leniently parsing the RDF dump.
+/// All of them are counted, while only the first ones are kept in detail.
+#[derive(Debug, Default, Clone)]
+pub struct ParseReport {
+    pub skipped: usize,
+    pub errors: Vec<SyntaxError>,
 }
 
 #[derive(Error, Debug)]
@@ -96,8 +151,20 @@ pub enum OpsError {
     PredicateNotFound,
     #[error("The provided object could not be found")]
     ObjectNotFound,
+    #[error("The provided graph could not be found")]
+    GraphNotFound,
     #[error("The array has not been loaded correctly")]
     EmptyArray,
     #[error("The sparse array has not been loaded correctly")]
     EmptySparseArray,
+    #[error("The triple could not be decoded using the Dictionary")]
+    TripleNotDecoded,
+    #[error("The Dictionary could not be read from the store: {0}")]
+    Dictionary(Box<RemoteHDTError>),
+}
+
+impl From<RemoteHDTError> for OpsError {
+    fn from(err: RemoteHDTError) -> Self {
+        OpsError::Dictionary(Box::new(err))
+    }
 }
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::storage::params::BlankNodePolicy;
+
+const GENID: &str = ".well-known/genid";
+
+/// Relabels the blank nodes of a source following a [`BlankNodePolicy`]. They
+/// are numbered in the order in which they first appear; hence, a source is
+/// always given the same labels, regardless of the ones it is written with.
+pub(crate) struct BlankNodes<'a> {
+    policy: &'a BlankNodePolicy,
+    scope: String,
+    labels: HashMap<String, usize>,
+}
+
+impl<'a> BlankNodes<'a> {
+    pub(crate) fn new(policy: &'a BlankNodePolicy, scope: &str) -> Self {
+        BlankNodes {
+            policy,
+            scope: scope.to_string(),
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Relabels a term written as in N-Triples, if it is a blank node
+    pub(crate) fn relabel(&mut self, term: String) -> String {
+        let label = match (self.policy, term.strip_prefix("_:")) {
+            (BlankNodePolicy::Keep, _) | (_, None) => return term,
+            (_, Some(label)) => label,
+        };
+        let id = match self.labels.get(label) {
+            Some(id) => *id,
+            None => {
+                let id = self.labels.len() + 1;
+                self.labels.insert(label.to_string(), id);
+                id
+            }
+        };
+
+        match self.policy {
+            BlankNodePolicy::Skolemize(base) => format!(
+                "<{}/{}/{}_{}>",
+                base.trim_end_matches('/'),
+                GENID,
+                self.scope,
+                id
+            ),
+            _ => format!("_:{}_{}", self.scope, id),
+        }
+    }
+}
+
+/// The scope of the blank nodes of a source: the one that is provided, or else
+/// the name of the file they are read from, next to a hash of its full path,
+/// so that files of the same name in different directories are told apart.
+/// There is no scope for the rest of the sources, as any name given to them
+/// could be given to other ones as well.
+pub(crate) fn scope(scope: Option<&str>, path: Option<&str>) -> Option<String> {
+    if let Some(scope) = scope {
+        return Some(sanitize(scope));
+    }
+    let path = path?;
+    let path = fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy(),
+        None => path.to_string_lossy(),
+    };
+    Some(format!(
+        "{}_{:016x}",
+        sanitize(&name),
+        fnv1a(path.to_string_lossy().as_bytes())
+    ))
+}
+
+/// Replaces the characters that are not allowed in the label of a blank node
+fn sanitize(scope: &str) -> String {
+    scope
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
+
+/// The 64-bit FNV-1a hash, which is used instead of the hasher of the standard
+/// library as it must not change between releases; otherwise, the same file
+/// would not be given the same labels
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
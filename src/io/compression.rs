@@ -0,0 +1,72 @@
+use bzip2::bufread::MultiBzDecoder;
+use flate2::bufread::MultiGzDecoder;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::error::ParserError;
+
+const GZIP_MAGIC_BYTES: &[u8] = &[0x1f, 0x8b];
+const BZIP2_MAGIC_BYTES: &[u8] = b"BZh";
+const ZSTD_MAGIC_BYTES: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// The extensions of the compressed files, which are skipped when guessing the
+/// format of an RDF dump from its path; e.g. `dump.nt.gz` is an N-Triples dump
+const EXTENSIONS: [&str; 5] = ["gz", "gzip", "bz2", "zst", "zstd"];
+
+enum Compression {
+    Gzip,
+    Bzip2,
+    Zstd,
+}
+
+impl Compression {
+    /// Compressed dumps are detected by their magic bytes, instead of by their
+    /// extension, so that readers can also be decompressed transparently
+    fn detect(header: &[u8]) -> Option<Self> {
+        if header.starts_with(GZIP_MAGIC_BYTES) {
+            Some(Compression::Gzip)
+        } else if header.starts_with(BZIP2_MAGIC_BYTES) {
+            Some(Compression::Bzip2)
+        } else if header.starts_with(ZSTD_MAGIC_BYTES) {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// Wraps the reader into the decoder of the compression it is using, if any;
+/// otherwise, the reader is returned as it is
+pub(crate) fn decompress(
+    mut reader: impl BufRead + 'static,
+) -> Result<Box<dyn BufRead>, ParserError> {
+    let compression = match reader.fill_buf() {
+        Ok(header) => Compression::detect(header),
+        Err(_) => return Err(ParserError::Decompression),
+    };
+
+    Ok(match compression {
+        // Several members may have been concatenated into a single file; as
+        // such, we keep on decoding until the end of the file is reached
+        Some(Compression::Gzip) => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+        Some(Compression::Bzip2) => Box::new(BufReader::new(MultiBzDecoder::new(reader))),
+        Some(Compression::Zstd) => match zstd::Decoder::with_buffer(reader) {
+            Ok(decoder) => Box::new(BufReader::new(decoder)),
+            Err(_) => return Err(ParserError::Decompression),
+        },
+        None => Box::new(reader),
+    })
+}
+
+/// Returns the extension that determines the format of the RDF dump, which is
+/// the one preceding the extension of the compression, if there is such
+pub(crate) fn format_extension(path: &str) -> Option<&str> {
+    let path = Path::new(path);
+    match path.extension()?.to_str()? {
+        extension if EXTENSIONS.contains(&extension.to_lowercase().as_str()) => {
+            Path::new(path.file_stem()?).extension()?.to_str()
+        }
+        extension => Some(extension),
+    }
+}
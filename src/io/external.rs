@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Result;
+use std::io::Seek;
+use std::io::Write;
+
+/// An encoded triple; that is, the indices of its first, second and third
+/// terms in the Dictionary, following the provided reference system.
+pub(crate) type EncodedTriple = (u32, u32, u32);
+
+/// A triple of a named graph; that is, the index of the graph, followed by the
+/// encoded triple itself.
+pub(crate) type EncodedQuad = (u32, u32, u32, u32);
+
+/// Elements that can be spilled to disk while they are being sorted.
+pub(crate) trait Record: Sized {
+    fn write_to(&self, writer: &mut impl Write) -> Result<()>;
+    fn read_from(reader: &mut impl Read) -> Result<Option<Self>>;
+}
+
+impl Record for String {
+    fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        writer.write_all(&(self.len() as u32).to_le_bytes())?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Option<Self>> {
+        let len = match read_u32(reader)? {
+            Some(len) => len as usize,
+            None => return Ok(None),
+        };
+        let mut bytes = vec![0; len];
+        reader.read_exact(&mut bytes)?;
+        match String::from_utf8(bytes) {
+            Ok(term) => Ok(Some(term)),
+            Err(err) => Err(Error::new(ErrorKind::InvalidData, err)),
+        }
+    }
+}
+
+impl Record for EncodedTriple {
+    fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        writer.write_all(&self.0.to_le_bytes())?;
+        writer.write_all(&self.1.to_le_bytes())?;
+        writer.write_all(&self.2.to_le_bytes())
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Option<Self>> {
+        let first = match read_u32(reader)? {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        let mut bytes = [0; 8];
+        reader.read_exact(&mut bytes)?;
+        let second = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        let third = u32::from_le_bytes(bytes[4..].try_into().unwrap());
+        Ok(Some((first, second, third)))
+    }
+}
+
+impl Record for EncodedQuad {
+    fn write_to(&self, writer: &mut impl Write) -> Result<()> {
+        writer.write_all(&self.0.to_le_bytes())?;
+        (self.1, self.2, self.3).write_to(writer)
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Option<Self>> {
+        let graph = match read_u32(reader)? {
+            Some(graph) => graph,
+            None => return Ok(None),
+        };
+        match EncodedTriple::read_from(reader)? {
+            Some((first, second, third)) => Ok(Some((graph, first, second, third))),
+            None => Err(Error::from(ErrorKind::UnexpectedEof)),
+        }
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<Option<u32>> {
+    let mut bytes = [0; 4];
+    match reader.read_exact(&mut bytes) {
+        Ok(_) => Ok(Some(u32::from_le_bytes(bytes))),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Sorts more elements than the ones that fit in memory. Elements are buffered
+/// until `capacity` is reached; then, the buffer is sorted and written into a
+/// temporary file, which is called a run. Once all the elements are pushed,
+/// the runs are merged lazily. Sorting is stable; that is, elements comparing
+/// equal are returned in the same order in which they were pushed, unless
+/// duplicates are removed, in which case only the first of them is kept.
+pub(crate) struct ExternalSorter<T: Record> {
+    capacity: usize,
+    compare: fn(&T, &T) -> Ordering,
+    dedup: bool,
+    buffer: Vec<T>,
+    runs: Vec<File>,
+    len: usize,
+}
+
+impl<T: Record> ExternalSorter<T> {
+    pub(crate) fn new(capacity: usize, compare: fn(&T, &T) -> Ordering, dedup: bool) -> Self {
+        ExternalSorter {
+            capacity: capacity.max(1),
+            compare,
+            dedup,
+            buffer: Vec::new(),
+            runs: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// The number of elements that have been pushed, duplicates included.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn push(&mut self, element: T) -> Result<()> {
+        self.buffer.push(element);
+        self.len += 1;
+        if self.buffer.len() >= self.capacity {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self) -> Result<Merge<T>> {
+        self.sort_buffer();
+        let mut runs = Vec::with_capacity(self.runs.len() + 1);
+        for mut file in self.runs {
+            file.rewind()?;
+            runs.push(Run::File(BufReader::new(file)));
+        }
+        // The elements that were not spilled are the last run to be merged
+        runs.push(Run::Memory(self.buffer.into_iter()));
+
+        Merge::new(runs, self.compare, self.dedup)
+    }
+
+    fn sort_buffer(&mut self) {
+        self.buffer.sort_by(self.compare);
+        if self.dedup {
+            self.buffer
+                .dedup_by(|a, b| (self.compare)(a, b) == Ordering::Equal);
+        }
+    }
+
+    fn spill(&mut self) -> Result<()> {
+        self.sort_buffer();
+        let mut writer = BufWriter::new(tempfile::tempfile()?);
+        for element in self.buffer.drain(..) {
+            element.write_to(&mut writer)?;
+        }
+        match writer.into_inner() {
+            Ok(file) => self.runs.push(file),
+            Err(err) => return Err(err.into_error()),
+        }
+        Ok(())
+    }
+}
+
+enum Run<T> {
+    File(BufReader<File>),
+    Memory(std::vec::IntoIter<T>),
+}
+
+impl<T: Record> Run<T> {
+    fn next(&mut self) -> Result<Option<T>> {
+        match self {
+            Run::File(reader) => T::read_from(reader),
+            Run::Memory(iter) => Ok(iter.next()),
+        }
+    }
+}
+
+/// The sorted elements of an [`ExternalSorter`]. The smallest head among the
+/// runs is returned at each step; ties are broken by the order of the runs, so
+/// that sorting remains stable. If an element cannot be read from disk, the
+/// iteration stops, and the error is available through [`Merge::error`].
+pub(crate) struct Merge<T: Record> {
+    runs: Vec<Run<T>>,
+    heads: Vec<Option<T>>,
+    compare: fn(&T, &T) -> Ordering,
+    dedup: bool,
+    error: Option<Error>,
+}
+
+impl<T: Record> Merge<T> {
+    fn new(mut runs: Vec<Run<T>>, compare: fn(&T, &T) -> Ordering, dedup: bool) -> Result<Self> {
+        let mut heads = Vec::with_capacity(runs.len());
+        for run in runs.iter_mut() {
+            heads.push(run.next()?);
+        }
+
+        Ok(Merge {
+            runs,
+            heads,
+            compare,
+            dedup,
+            error: None,
+        })
+    }
+
+    pub(crate) fn error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    fn pop(&mut self) -> Result<Option<T>> {
+        let mut min: Option<usize> = None;
+        for (i, head) in self.heads.iter().enumerate() {
+            if let Some(head) = head {
+                min = match min {
+                    Some(j)
+                        if (self.compare)(head, self.heads[j].as_ref().unwrap())
+                            != Ordering::Less =>
+                    {
+                        Some(j)
+                    }
+                    _ => Some(i),
+                };
+            }
+        }
+
+        match min {
+            Some(i) => {
+                let next = self.runs[i].next()?;
+                Ok(std::mem::replace(&mut self.heads[i], next))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: Record> Iterator for Merge<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = match self.pop() {
+            Ok(element) => element?,
+            Err(err) => {
+                self.error = Some(err);
+                return None;
+            }
+        };
+
+        if self.dedup {
+            // As the runs are sorted, the duplicates of this element, if any,
+            // are the following heads to be popped from them
+            while self
+                .heads
+                .iter()
+                .flatten()
+                .any(|head| (self.compare)(head, &element) == Ordering::Equal)
+            {
+                if let Err(err) = self.pop() {
+                    self.error = Some(err);
+                    return None;
+                }
+            }
+        }
+
+        Some(element)
+    }
+}
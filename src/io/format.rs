@@ -0,0 +1,95 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::ParserError;
+use crate::storage::params::RdfFormat;
+
+use super::compression::format_extension;
+use super::open;
+
+/// Guesses the format of an RDF dump from the extension of its path; if it
+/// has no known extension, the first bytes of the dump are read instead
+pub(crate) fn guess_format(path: &str) -> Result<RdfFormat, ParserError> {
+    let extension = format_extension(path);
+    if let Some(Ok(format)) = extension.map(RdfFormat::from_str) {
+        return Ok(format);
+    }
+
+    match (sniff(&mut open(path)?), extension) {
+        (Some(format), _) => Ok(format),
+        (None, Some(extension)) => Err(ParserError::NotSupportedFormat(extension.to_string())),
+        (None, None) => Err(ParserError::NoFormatProvided),
+    }
+}
+
+/// Guesses the format of an RDF dump from its first statement, without
+/// consuming the reader. Line-based dumps are told apart by the number of
+/// terms in the statement, while TriG is assumed if there are any graphs
+fn sniff(reader: &mut dyn BufRead) -> Option<RdfFormat> {
+    let header = String::from_utf8_lossy(reader.fill_buf().ok()?);
+    let first = header
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))?;
+    let turtle = match header.contains('{') {
+        true => RdfFormat::TriG,
+        false => RdfFormat::Turtle,
+    };
+
+    if first.starts_with("$HDT") {
+        return Some(RdfFormat::Hdt);
+    }
+    if first.starts_with("<?xml") || first.starts_with("<rdf:RDF") {
+        return Some(RdfFormat::RdfXml);
+    }
+    // Either a JSON object or an array of them
+    if first.starts_with('{') || first.trim_start_matches('[').trim_start().starts_with('{') {
+        return Some(RdfFormat::JsonLd);
+    }
+
+    match first.split_whitespace().next()?.to_lowercase().as_str() {
+        "@prefix" | "@base" | "prefix" | "base" => Some(turtle),
+        term if term.starts_with('<') || term.starts_with("_:") => match count_terms(first) {
+            Some(3) => Some(RdfFormat::NTriples),
+            Some(4) => Some(RdfFormat::NQuads),
+            _ => Some(turtle),
+        },
+        term if term.starts_with('[') || term.starts_with('(') => Some(turtle),
+        _ => None,
+    }
+}
+
+/// Counts the terms of an N-Triples or N-Quads statement, which is expected to
+/// be terminated by a dot; otherwise, `None` is returned
+fn count_terms(statement: &str) -> Option<usize> {
+    let mut chars = statement.chars().peekable();
+    let mut terms = 0;
+    loop {
+        match chars.next()? {
+            ' ' | '\t' => continue,
+            '.' => return chars.all(char::is_whitespace).then_some(terms),
+            '<' => chars.by_ref().find(|&c| c == '>').map(|_| ())?,
+            '_' => while chars.next_if(|c| !c.is_whitespace()).is_some() {},
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => chars.next().map(|_| ())?,
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+                // The datatype or the language tag of the literal
+                match chars.peek() {
+                    Some('^') => while chars.next_if(|&c| c != '>').is_some() {},
+                    Some('@') => while chars.next_if(|c| !c.is_whitespace()).is_some() {},
+                    _ => {}
+                }
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+        terms += 1;
+    }
+}
@@ -0,0 +1,173 @@
+use rio_api::formatter::TriplesFormatter;
+use rio_api::model::BlankNode;
+use rio_api::model::Literal;
+use rio_api::model::NamedNode;
+use rio_api::model::Subject;
+use rio_api::model::Term;
+use rio_api::model::Triple;
+use rio_turtle::NTriplesFormatter;
+use rio_turtle::TurtleFormatter;
+use std::io::Write;
+
+use crate::error::RemoteHDTError;
+use crate::storage::params::RdfFormat;
+
+use super::StringTriple;
+
+/// A writer of the triples stored in the Dictionary, whose terms are kept in
+/// their N-Triples form; as such, they are parsed back into terms before
+/// being handed to the corresponding formatter
+pub(crate) enum RdfFormatter<W: Write> {
+    NTriples(NTriplesFormatter<W>),
+    Turtle(TurtleFormatter<W>),
+}
+
+impl<W: Write> RdfFormatter<W> {
+    pub(crate) fn new(writer: W, format: RdfFormat) -> Result<Self, RemoteHDTError> {
+        match format {
+            RdfFormat::NTriples => Ok(RdfFormatter::NTriples(NTriplesFormatter::new(writer))),
+            RdfFormat::Turtle => Ok(RdfFormatter::Turtle(TurtleFormatter::new(writer))),
+            format => Err(RemoteHDTError::NotSupportedExport(format)),
+        }
+    }
+
+    pub(crate) fn format(&mut self, triple: &StringTriple) -> Result<(), RemoteHDTError> {
+        let (subject, predicate, object) = triple;
+        let subject = match parse_term(subject) {
+            Some(ParsedTerm::NamedNode(iri)) => Subject::NamedNode(NamedNode { iri }),
+            Some(ParsedTerm::BlankNode(id)) => Subject::BlankNode(BlankNode { id }),
+            _ => return Err(RemoteHDTError::TermNotExported(subject.to_owned())),
+        };
+        let predicate = match parse_term(predicate) {
+            Some(ParsedTerm::NamedNode(iri)) => NamedNode { iri },
+            _ => return Err(RemoteHDTError::TermNotExported(predicate.to_owned())),
+        };
+        let value;
+        let object = match parse_term(object) {
+            Some(ParsedTerm::NamedNode(iri)) => Term::NamedNode(NamedNode { iri }),
+            Some(ParsedTerm::BlankNode(id)) => Term::BlankNode(BlankNode { id }),
+            Some(ParsedTerm::Literal(lexical, suffix)) => {
+                value = lexical;
+                Term::Literal(match suffix {
+                    LiteralSuffix::None => Literal::Simple { value: &value },
+                    LiteralSuffix::Language(language) => Literal::LanguageTaggedString {
+                        value: &value,
+                        language,
+                    },
+                    LiteralSuffix::Datatype(iri) => Literal::Typed {
+                        value: &value,
+                        datatype: NamedNode { iri },
+                    },
+                })
+            }
+            None => return Err(RemoteHDTError::TermNotExported(object.to_owned())),
+        };
+
+        let triple = Triple {
+            subject,
+            predicate,
+            object,
+        };
+        match self {
+            RdfFormatter::NTriples(formatter) => formatter.format(&triple)?,
+            RdfFormatter::Turtle(formatter) => formatter.format(&triple)?,
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> Result<W, RemoteHDTError> {
+        Ok(match self {
+            RdfFormatter::NTriples(formatter) => formatter.finish()?,
+            RdfFormatter::Turtle(formatter) => formatter.finish()?,
+        })
+    }
+}
+
+pub(crate) enum ParsedTerm<'a> {
+    NamedNode(&'a str),
+    BlankNode(&'a str),
+    Literal(String, LiteralSuffix<'a>),
+}
+
+pub(crate) enum LiteralSuffix<'a> {
+    None,
+    Language(&'a str),
+    Datatype(&'a str),
+}
+
+/// Parses a term written in its N-Triples form, which is how the terms are
+/// stored in the Dictionary; `None` is returned if the term is malformed
+pub(crate) fn parse_term(term: &str) -> Option<ParsedTerm<'_>> {
+    if let Some(iri) = term.strip_prefix('<') {
+        return Some(ParsedTerm::NamedNode(iri.strip_suffix('>')?));
+    }
+    if let Some(id) = term.strip_prefix("_:") {
+        return Some(ParsedTerm::BlankNode(id));
+    }
+
+    // The closing quote is the first one that has not been escaped, as the
+    // lexical form is followed either by nothing, a language or a datatype
+    let quoted = term.strip_prefix('"')?;
+    let mut escaped = false;
+    let end = quoted
+        .char_indices()
+        .find_map(|(idx, c)| match (escaped, c) {
+            (true, _) => {
+                escaped = false;
+                None
+            }
+            (false, '\\') => {
+                escaped = true;
+                None
+            }
+            (false, '"') => Some(idx),
+            (false, _) => None,
+        })?;
+
+    let suffix = match &quoted[end + 1..] {
+        "" => LiteralSuffix::None,
+        suffix => match (suffix.strip_prefix('@'), suffix.strip_prefix("^^<")) {
+            (Some(language), _) => LiteralSuffix::Language(language),
+            (_, Some(iri)) => LiteralSuffix::Datatype(iri.strip_suffix('>')?),
+            _ => return None,
+        },
+    };
+
+    Some(ParsedTerm::Literal(unescape(&quoted[..end])?, suffix))
+}
+
+/// Undoes the escaping of the lexical form of a literal, so that it is not
+/// escaped twice by the formatter
+fn unescape(lexical: &str) -> Option<String> {
+    let mut value = String::with_capacity(lexical.len());
+    let mut chars = lexical.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        value.push(match chars.next()? {
+            't' => '\t',
+            'b' => '\u{8}',
+            'n' => '\n',
+            'r' => '\r',
+            'f' => '\u{c}',
+            '"' => '"',
+            '\'' => '\'',
+            '\\' => '\\',
+            'u' => unescape_code_point(&mut chars, 4)?,
+            'U' => unescape_code_point(&mut chars, 8)?,
+            _ => return None,
+        });
+    }
+    Some(value)
+}
+
+fn unescape_code_point(chars: &mut std::str::Chars, digits: usize) -> Option<char> {
+    let code_point = chars.by_ref().take(digits).collect::<String>();
+    match code_point.len() == digits {
+        true => char::from_u32(u32::from_str_radix(&code_point, 16).ok()?),
+        false => None,
+    }
+}
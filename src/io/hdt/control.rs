@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use crate::error::ParserError;
+
+use super::encoding::check_crc16;
+use super::encoding::read_byte;
+use super::encoding::write_crc16;
+use super::HdtResult;
+
+const MAGIC: &[u8] = b"$HDT";
+
+/// The component of the HDT file that the control information precedes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Component {
+    Global = 1,
+    Header = 2,
+    Dictionary = 3,
+    Triples = 4,
+}
+
+/// The control information that precedes every component of an HDT file,
+/// which tells its format and a set of `key=value;` properties
+pub(super) struct ControlInfo {
+    pub(super) format: String,
+    properties: HashMap<String, String>,
+}
+
+impl ControlInfo {
+    pub(super) fn read(reader: &mut impl Read, component: Component) -> HdtResult<Self> {
+        let mut checked = Vec::new();
+        for &expected in MAGIC {
+            if read_byte(reader, &mut checked)? != expected {
+                return Err(ParserError::Hdt(String::from("missing HDT cookie")));
+            }
+        }
+        if read_byte(reader, &mut checked)? != component as u8 {
+            return Err(ParserError::Hdt(format!("expected the {:?}", component)));
+        }
+        let format = read_cstring(reader, &mut checked)?;
+        let properties = read_cstring(reader, &mut checked)?;
+        check_crc16(reader, &checked)?;
+
+        Ok(ControlInfo {
+            format,
+            properties: properties
+                .split(';')
+                .filter_map(|property| property.split_once('='))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        })
+    }
+
+    pub(super) fn write(
+        writer: &mut impl Write,
+        component: Component,
+        format: &str,
+        properties: &[(&str, u64)],
+    ) -> IoResult<()> {
+        let mut checked = MAGIC.to_vec();
+        checked.push(component as u8);
+        checked.extend_from_slice(format.as_bytes());
+        checked.push(0);
+        properties.iter().for_each(|(key, value)| {
+            checked.extend_from_slice(format!("{}={};", key, value).as_bytes())
+        });
+        checked.push(0);
+        write_crc16(writer, &checked)
+    }
+
+    pub(super) fn property(&self, key: &str) -> HdtResult<u64> {
+        match self.properties.get(key).map(|value| value.parse()) {
+            Some(Ok(value)) => Ok(value),
+            _ => Err(ParserError::Hdt(format!("missing property {}", key))),
+        }
+    }
+}
+
+/// Reads a string that is terminated by a null byte
+fn read_cstring(reader: &mut impl Read, checked: &mut Vec<u8>) -> HdtResult<String> {
+    let mut bytes = Vec::new();
+    loop {
+        match read_byte(reader, checked)? {
+            0 => break,
+            byte => bytes.push(byte),
+        }
+    }
+    match String::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        Err(err) => Err(ParserError::Hdt(err.to_string())),
+    }
+}
@@ -0,0 +1,185 @@
+use rio_api::model::Literal;
+use rio_api::model::NamedNode;
+use std::io::Read;
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use crate::error::ParserError;
+use crate::error::RemoteHDTError;
+use crate::io::formatter::parse_term;
+use crate::io::formatter::LiteralSuffix;
+use crate::io::formatter::ParsedTerm;
+
+use super::encoding::check_crc32;
+use super::encoding::check_crc8;
+use super::encoding::decode_vbyte;
+use super::encoding::read_byte;
+use super::encoding::read_bytes;
+use super::encoding::read_vbyte;
+use super::encoding::write_crc32;
+use super::encoding::write_crc8;
+use super::encoding::write_vbyte;
+use super::encoding::LogSequence;
+use super::HdtResult;
+
+const TYPE_FRONT_CODING: u8 = 2;
+const BLOCK_SIZE: usize = 16;
+
+/// Reads a front-coded section of the Dictionary into its terms, which are
+/// written as in N-Triples. The strings are split into blocks, whose first
+/// string is kept as it is, while the rest only keep their suffix, preceded
+/// by the length of the prefix they share with the previous one.
+pub(super) fn read_section(reader: &mut impl Read) -> HdtResult<Vec<String>> {
+    let mut checked = Vec::new();
+    if read_byte(reader, &mut checked)? != TYPE_FRONT_CODING {
+        return Err(ParserError::Hdt(String::from(
+            "unsupported dictionary section type",
+        )));
+    }
+    let len = read_vbyte(reader, &mut checked)? as usize;
+    let bytes = read_vbyte(reader, &mut checked)? as usize;
+    let block_size = read_vbyte(reader, &mut checked)? as usize;
+    check_crc8(reader, &checked)?;
+
+    // The strings are decoded one after the other; hence, the offsets of
+    // the blocks are not needed, although they have to be consumed
+    LogSequence::read(reader)?;
+    let text = read_bytes(reader, bytes)?;
+    check_crc32(reader, &text)?;
+
+    let mut terms = Vec::with_capacity(len);
+    let mut previous: Vec<u8> = Vec::new();
+    let mut position = 0;
+    for idx in 0..len {
+        let mut term = match idx % block_size.max(1) {
+            0 => Vec::new(),
+            _ => match decode_vbyte(&text[position..]) {
+                Some((prefix, read)) if prefix as usize <= previous.len() => {
+                    position += read;
+                    previous[..prefix as usize].to_vec()
+                }
+                _ => return Err(ParserError::Hdt(String::from("invalid shared prefix"))),
+            },
+        };
+        let suffix = match text[position..].iter().position(|&byte| byte == 0) {
+            Some(end) => &text[position..position + end],
+            None => return Err(ParserError::Hdt(String::from("unterminated string"))),
+        };
+        position += suffix.len() + 1;
+        term.extend_from_slice(suffix);
+
+        match std::str::from_utf8(&term) {
+            Ok(string) => terms.push(to_ntriples(string)),
+            Err(err) => return Err(ParserError::Hdt(err.to_string())),
+        }
+        previous = term;
+    }
+
+    Ok(terms)
+}
+
+/// HDT keeps IRIs without their angle brackets, and the lexical forms of the
+/// literals without escaping them; as such, they are written back as in the
+/// rest of the formats
+fn to_ntriples(term: &str) -> String {
+    if term.starts_with("_:") {
+        return term.to_string();
+    }
+    let (lexical, suffix) = match term
+        .strip_prefix('"')
+        .and_then(|term| term.rsplit_once('"'))
+    {
+        Some(literal) => literal,
+        None => return NamedNode { iri: term }.to_string(),
+    };
+    match (suffix.strip_prefix('@'), suffix.strip_prefix("^^<")) {
+        (Some(language), _) => Literal::LanguageTaggedString {
+            value: lexical,
+            language,
+        }
+        .to_string(),
+        (_, Some(datatype)) => Literal::Typed {
+            value: lexical,
+            datatype: NamedNode {
+                iri: datatype.trim_end_matches('>'),
+            },
+        }
+        .to_string(),
+        _ => Literal::Simple { value: lexical }.to_string(),
+    }
+}
+
+/// A section of the Dictionary whose terms, which are expected to be sorted,
+/// have been front-coded following the same layout as [`read_section`]
+pub(super) struct Section {
+    len: usize,
+    text: Vec<u8>,
+    blocks: Vec<u64>,
+}
+
+impl Section {
+    pub(super) fn new(terms: &[String]) -> Self {
+        let mut text = Vec::new();
+        let mut blocks = Vec::new();
+        let mut previous: &[u8] = &[];
+        for (idx, term) in terms.iter().enumerate() {
+            let term = term.as_bytes();
+            let prefix = match idx % BLOCK_SIZE {
+                0 => {
+                    blocks.push(text.len() as u64);
+                    0
+                }
+                _ => {
+                    let prefix = term
+                        .iter()
+                        .zip(previous)
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    write_vbyte(&mut text, prefix as u64);
+                    prefix
+                }
+            };
+            text.extend_from_slice(&term[prefix..]);
+            text.push(0);
+            previous = term;
+        }
+        blocks.push(text.len() as u64);
+
+        Section {
+            len: terms.len(),
+            text,
+            blocks,
+        }
+    }
+
+    /// The number of bytes taken by the front-coded terms
+    pub(super) fn size(&self) -> u64 {
+        self.text.len() as u64
+    }
+
+    pub(super) fn write(&self, writer: &mut impl Write) -> IoResult<()> {
+        let mut header = vec![TYPE_FRONT_CODING];
+        write_vbyte(&mut header, self.len as u64);
+        write_vbyte(&mut header, self.text.len() as u64);
+        write_vbyte(&mut header, BLOCK_SIZE as u64);
+        write_crc8(writer, &header)?;
+        LogSequence::write(writer, &self.blocks)?;
+        write_crc32(writer, &self.text)
+    }
+}
+
+/// The inverse of [`to_ntriples`], which writes the term as HDT keeps it
+pub(super) fn to_hdt(term: &str) -> Result<String, RemoteHDTError> {
+    Ok(match parse_term(term) {
+        Some(ParsedTerm::NamedNode(iri)) => iri.to_string(),
+        Some(ParsedTerm::BlankNode(_)) => term.to_string(),
+        Some(ParsedTerm::Literal(value, LiteralSuffix::None)) => format!("\"{}\"", value),
+        Some(ParsedTerm::Literal(value, LiteralSuffix::Language(language))) => {
+            format!("\"{}\"@{}", value, language)
+        }
+        Some(ParsedTerm::Literal(value, LiteralSuffix::Datatype(datatype))) => {
+            format!("\"{}\"^^<{}>", value, datatype)
+        }
+        None => return Err(RemoteHDTError::TermNotExported(term.to_string())),
+    })
+}
@@ -0,0 +1,286 @@
+use std::io::Read;
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use crate::error::ParserError;
+
+use super::HdtResult;
+
+const TYPE_SEQUENCE_LOG: u8 = 1;
+const TYPE_BITMAP_PLAIN: u8 = 1;
+
+/// Reads a single byte, which is also appended to the bytes covered by the
+/// checksum of the component being read
+pub(super) fn read_byte(reader: &mut impl Read, checked: &mut Vec<u8>) -> HdtResult<u8> {
+    let mut byte = [0; 1];
+    read_exact(reader, &mut byte)?;
+    checked.push(byte[0]);
+    Ok(byte[0])
+}
+
+pub(super) fn read_bytes(reader: &mut impl Read, len: usize) -> HdtResult<Vec<u8>> {
+    let mut bytes = vec![0; len];
+    read_exact(reader, &mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_exact(reader: &mut impl Read, buffer: &mut [u8]) -> HdtResult<()> {
+    match reader.read_exact(buffer) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(ParserError::Hdt(err.to_string())),
+    }
+}
+
+/// Reads a variable-length integer. Every byte holds 7 bits of the value,
+/// starting from the least significant ones, while the most significant bit
+/// of the byte marks the last one of the integer
+pub(super) fn read_vbyte(reader: &mut impl Read, checked: &mut Vec<u8>) -> HdtResult<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = read_byte(reader, checked)?;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 != 0 {
+            return Ok(value);
+        }
+    }
+    Err(ParserError::Hdt(String::from(
+        "variable-length integer overflow",
+    )))
+}
+
+pub(super) fn write_vbyte(bytes: &mut Vec<u8>, mut value: u64) {
+    while value > 0x7f {
+        bytes.push((value & 0x7f) as u8);
+        value >>= 7;
+    }
+    bytes.push(value as u8 | 0x80);
+}
+
+/// Decodes a variable-length integer from the start of the buffer, returning
+/// it next to the number of bytes it takes
+pub(super) fn decode_vbyte(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0;
+    for (idx, byte) in bytes.iter().take(10).enumerate() {
+        value |= ((byte & 0x7f) as u64) << (7 * idx);
+        if byte & 0x80 != 0 {
+            return Some((value, idx + 1));
+        }
+    }
+    None
+}
+
+pub(super) fn check_crc8(reader: &mut impl Read, checked: &[u8]) -> HdtResult<()> {
+    let expected = read_bytes(reader, 1)?;
+    match crc8(checked) == expected[0] {
+        true => Ok(()),
+        false => Err(ParserError::Hdt(String::from("CRC8 checksum mismatch"))),
+    }
+}
+
+pub(super) fn check_crc16(reader: &mut impl Read, checked: &[u8]) -> HdtResult<()> {
+    let expected = read_bytes(reader, 2)?;
+    match crc16(checked) == u16::from_le_bytes([expected[0], expected[1]]) {
+        true => Ok(()),
+        false => Err(ParserError::Hdt(String::from("CRC16 checksum mismatch"))),
+    }
+}
+
+pub(super) fn check_crc32(reader: &mut impl Read, checked: &[u8]) -> HdtResult<()> {
+    let expected = read_bytes(reader, 4)?;
+    match crc32c::crc32c(checked).to_le_bytes() == expected[..] {
+        true => Ok(()),
+        false => Err(ParserError::Hdt(String::from("CRC32 checksum mismatch"))),
+    }
+}
+
+/// Writes the bytes, followed by their checksum
+pub(super) fn write_crc8(writer: &mut impl Write, checked: &[u8]) -> IoResult<()> {
+    writer.write_all(checked)?;
+    writer.write_all(&[crc8(checked)])
+}
+
+pub(super) fn write_crc16(writer: &mut impl Write, checked: &[u8]) -> IoResult<()> {
+    writer.write_all(checked)?;
+    writer.write_all(&crc16(checked).to_le_bytes())
+}
+
+pub(super) fn write_crc32(writer: &mut impl Write, checked: &[u8]) -> IoResult<()> {
+    writer.write_all(checked)?;
+    writer.write_all(&crc32c::crc32c(checked).to_le_bytes())
+}
+
+/// CRC-8-CCITT, which protects the headers of the sections
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |crc, byte| {
+        (0..8).fold(crc ^ byte, |crc, _| match crc & 0x80 {
+            0 => crc << 1,
+            _ => (crc << 1) ^ 0x07,
+        })
+    })
+}
+
+/// CRC-16-ANSI, which protects the control information
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u16, |crc, _| match crc & 1 {
+            0 => crc >> 1,
+            _ => (crc >> 1) ^ 0xa001,
+        })
+    })
+}
+
+/// Packs the little-endian bytes into words, the last of which is padded
+fn to_words(bytes: &[u8]) -> Vec<u64> {
+    bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(word)
+        })
+        .collect()
+}
+
+/// Values packed into as many bytes as needed to hold their bits, starting
+/// from the least significant ones. They are packed as they are pushed, so
+/// that sequences and bitmaps are built without keeping their values apart.
+pub(super) struct Packed {
+    bits: usize,
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+impl Packed {
+    pub(super) fn new(bits: usize) -> Self {
+        Packed {
+            bits,
+            len: 0,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// The number of bits taken by the values up to the provided one
+    pub(super) fn width(max: u64) -> usize {
+        (u64::BITS - max.leading_zeros()) as usize
+    }
+
+    pub(super) fn push(&mut self, value: u64) {
+        let start = self.len * self.bits;
+        self.len += 1;
+        self.bytes.resize((self.len * self.bits).div_ceil(8), 0);
+        (0..self.bits)
+            .filter(|bit| value >> bit & 1 == 1)
+            .map(|bit| start + bit)
+            .for_each(|position| self.bytes[position / 8] |= 1 << (position % 8));
+    }
+}
+
+/// Reads the packed data of a sequence or a bitmap, which takes as many bytes
+/// as needed to hold its bits, followed by its checksum
+fn read_words(reader: &mut impl Read, bits: u64) -> HdtResult<Vec<u64>> {
+    let data = read_bytes(reader, bits.div_ceil(8) as usize)?;
+    check_crc32(reader, &data)?;
+    Ok(to_words(&data))
+}
+
+/// A sequence of integers, each of which takes the same number of bits
+pub(super) struct LogSequence {
+    bits: usize,
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl LogSequence {
+    pub(super) fn read(reader: &mut impl Read) -> HdtResult<Self> {
+        let mut checked = Vec::new();
+        if read_byte(reader, &mut checked)? != TYPE_SEQUENCE_LOG {
+            return Err(ParserError::Hdt(String::from("unsupported sequence type")));
+        }
+        let bits = read_byte(reader, &mut checked)? as usize;
+        let len = read_vbyte(reader, &mut checked)? as usize;
+        check_crc8(reader, &checked)?;
+
+        if bits > 64 {
+            return Err(ParserError::Hdt(String::from("invalid sequence width")));
+        }
+
+        Ok(LogSequence {
+            bits,
+            len,
+            words: read_words(reader, (bits * len) as u64)?,
+        })
+    }
+
+    /// Writes the values using as many bits for each of them as the largest
+    /// one needs
+    pub(super) fn write(writer: &mut impl Write, values: &[u64]) -> IoResult<()> {
+        let mut packed = Packed::new(Packed::width(values.iter().max().copied().unwrap_or(0)));
+        values.iter().for_each(|&value| packed.push(value));
+        LogSequence::write_packed(writer, &packed)
+    }
+
+    pub(super) fn write_packed(writer: &mut impl Write, packed: &Packed) -> IoResult<()> {
+        let mut header = vec![TYPE_SEQUENCE_LOG, packed.bits as u8];
+        write_vbyte(&mut header, packed.len as u64);
+        write_crc8(writer, &header)?;
+        write_crc32(writer, &packed.bytes)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(super) fn get(&self, index: usize) -> u64 {
+        if self.bits == 0 {
+            return 0;
+        }
+        let position = index * self.bits;
+        let (word, offset) = (position / 64, position % 64);
+        let mut value = self.words[word] >> offset;
+        if offset + self.bits > 64 {
+            value |= self.words[word + 1] << (64 - offset);
+        }
+        match self.bits {
+            64 => value,
+            bits => value & ((1 << bits) - 1),
+        }
+    }
+}
+
+/// A sequence of bits, which marks the end of the groups of a sequence
+pub(super) struct Bitmap {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl Bitmap {
+    pub(super) fn read(reader: &mut impl Read) -> HdtResult<Self> {
+        let mut checked = Vec::new();
+        if read_byte(reader, &mut checked)? != TYPE_BITMAP_PLAIN {
+            return Err(ParserError::Hdt(String::from("unsupported bitmap type")));
+        }
+        let len = read_vbyte(reader, &mut checked)? as usize;
+        check_crc8(reader, &checked)?;
+
+        Ok(Bitmap {
+            len,
+            words: read_words(reader, len as u64)?,
+        })
+    }
+
+    /// Writes the bits, which are expected to be packed one by one
+    pub(super) fn write_packed(writer: &mut impl Write, packed: &Packed) -> IoResult<()> {
+        let mut header = vec![TYPE_BITMAP_PLAIN];
+        write_vbyte(&mut header, packed.len as u64);
+        write_crc8(writer, &header)?;
+        write_crc32(writer, &packed.bytes)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(super) fn get(&self, index: usize) -> bool {
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+}
@@ -0,0 +1,439 @@
+use fcsd::Set;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
+
+use crate::dictionary::partition::cmp_terms;
+use crate::dictionary::Dictionary;
+use crate::error::OpsError;
+use crate::error::ParserError;
+use crate::error::RemoteHDTError;
+use crate::storage::params::ReferenceSystem;
+use crate::storage::StorageResult;
+
+use self::control::Component;
+use self::control::ControlInfo;
+use self::dictionary::read_section;
+use self::dictionary::to_hdt;
+use self::dictionary::Section;
+use self::encoding::read_bytes;
+use self::triples::BitmapTriples;
+
+use super::external::EncodedTriple;
+use super::external::ExternalSorter;
+use super::open;
+use super::order;
+use super::Graph;
+use super::ParserOptions;
+use super::RdfParserResult;
+use super::RdfStreamResult;
+
+mod control;
+mod dictionary;
+mod encoding;
+mod triples;
+
+type HdtResult<T> = Result<T, ParserError>;
+type WriteResult = Result<(), RemoteHDTError>;
+
+const HDT_V1: &str = "<http://purl.org/HDT/hdt#HDTv1>";
+const DICTIONARY_FOUR: &str = "<http://purl.org/HDT/hdt#dictionaryFour>";
+const HEADER_NTRIPLES: &str = "ntriples";
+
+/// Imports and exports HDT files, whose Dictionary and triples are read and
+/// written directly instead of parsing their terms. As the triples are already
+/// encoded, the identifiers of HDT are only mapped to the ones of the
+/// Dictionary, and vice versa.
+pub(crate) struct Hdt;
+
+impl Hdt {
+    pub(crate) fn parse(
+        path: &str,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+    ) -> RdfParserResult {
+        Self::parse_reader(open(path)?, reference_system, options)
+    }
+
+    pub(crate) fn parse_reader(
+        mut reader: Box<dyn BufRead>,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+    ) -> RdfParserResult {
+        let hdt = HdtFile::read(&mut reader, reference_system, options)?;
+
+        let mut graph: Graph = vec![Vec::new(); hdt.first_term_size()];
+        for triple in hdt.encoded_triples() {
+            let (first, second, third) = triple?;
+            graph[first as usize].push((second, third))
+        }
+
+        Ok((graph, hdt.dictionary, Vec::new()))
+    }
+
+    /// The triples of the file are sorted in the order in which they were
+    /// written; hence, they have to be sorted by their first term once again
+    pub(crate) fn parse_streaming(
+        path: &str,
+        reference_system: &ReferenceSystem,
+        buffer_size: usize,
+        options: &ParserOptions,
+    ) -> RdfStreamResult {
+        let hdt = HdtFile::read(&mut open(path)?, reference_system, options)?;
+
+        let mut triples = ExternalSorter::new(
+            buffer_size,
+            |a: &EncodedTriple, b: &EncodedTriple| a.0.cmp(&b.0),
+            false,
+        );
+        for triple in hdt.encoded_triples() {
+            if let Err(err) = triples.push(triple?) {
+                return Err(ParserError::Graph(err.to_string()));
+            }
+        }
+
+        let graph_size = triples.len();
+        match triples.finish() {
+            Ok(triples) => Ok((triples, graph_size, hdt.dictionary, None)),
+            Err(err) => Err(ParserError::Graph(err.to_string())),
+        }
+    }
+
+    /// Writes the triples, given by the indices of their subject, predicate
+    /// and object in the Dictionary, into an HDT file. Only the terms that
+    /// appear in the triples are written, as every subject in HDT must have
+    /// at least one triple; hence, a single graph can also be exported. The
+    /// triples are visited twice: first, to find the terms that are used,
+    /// and then, to sort them by their HDT identifiers. Only the encoded
+    /// triples are bounded by `capacity`, as the rest of them are spilled to
+    /// disk; the terms that are used, both their indices and their decoded
+    /// values, and the mapping to their HDT identifiers are fully kept in
+    /// memory, so the memory needed grows with the vocabulary of the graph.
+    pub(crate) fn write<W: Write>(
+        mut writer: W,
+        dictionary: &Dictionary,
+        capacity: usize,
+        triples: impl Fn(&mut dyn FnMut(usize, usize, usize) -> WriteResult) -> WriteResult,
+    ) -> Result<W, RemoteHDTError> {
+        let (mut subjects, mut predicates, mut objects) =
+            (BTreeSet::new(), BTreeSet::new(), BTreeSet::new());
+        let mut len = 0;
+        triples(&mut |subject, predicate, object| {
+            subjects.insert(subject);
+            predicates.insert(predicate);
+            objects.insert(object);
+            len += 1;
+            Ok(())
+        })?;
+        if len == 0 {
+            return Err(RemoteHDTError::EmptyGraph);
+        }
+
+        let subjects = used_terms(subjects, |indices| {
+            dictionary.try_decode_subjects(indices).collect()
+        })?;
+        let mut predicates = used_terms(predicates, |indices| {
+            dictionary.try_decode_predicates(indices).collect()
+        })?;
+        let mut objects = used_terms(objects, |indices| {
+            dictionary.try_decode_objects(indices).collect()
+        })?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        // The terms that are both subjects and objects are moved to the shared
+        // section, whose identifiers precede the ones of both sections
+        let (mut shared, mut subjects_only) = (Vec::new(), Vec::new());
+        for (term, subject) in subjects {
+            match objects.remove(&term) {
+                Some(object) => shared.push((term, (subject, object))),
+                None => subjects_only.push((term, subject)),
+            }
+        }
+        // HDT sorts the terms without their angle brackets; as such, their
+        // order may differ from the one in the Dictionary
+        let mut objects_only = objects.into_iter().collect::<Vec<_>>();
+        shared.sort_unstable();
+        predicates.sort_unstable();
+        subjects_only.sort_unstable();
+        objects_only.sort_unstable();
+
+        let mut subject_ids = HashMap::new();
+        let mut object_ids = HashMap::new();
+        shared
+            .iter()
+            .enumerate()
+            .for_each(|(id, (_, (subject, object)))| {
+                subject_ids.insert(*subject, id as u32 + 1);
+                object_ids.insert(*object, id as u32 + 1);
+            });
+        subjects_only
+            .iter()
+            .enumerate()
+            .for_each(|(id, (_, subject))| {
+                subject_ids.insert(*subject, (shared.len() + id) as u32 + 1);
+            });
+        objects_only
+            .iter()
+            .enumerate()
+            .for_each(|(id, (_, object))| {
+                object_ids.insert(*object, (shared.len() + id) as u32 + 1);
+            });
+        let predicate_ids = predicates
+            .iter()
+            .enumerate()
+            .map(|(id, (_, predicate))| (*predicate, id as u32 + 1))
+            .collect::<HashMap<_, _>>();
+
+        let shared = shared.into_iter().map(|(term, _)| term).collect::<Vec<_>>();
+        let subjects_only = subjects_only
+            .into_iter()
+            .map(|(term, _)| term)
+            .collect::<Vec<_>>();
+        let predicates = predicates
+            .into_iter()
+            .map(|(term, _)| term)
+            .collect::<Vec<_>>();
+        let objects_only = objects_only
+            .into_iter()
+            .map(|(term, _)| term)
+            .collect::<Vec<_>>();
+
+        ControlInfo::write(&mut writer, Component::Global, HDT_V1, &[])?;
+
+        let header = header(
+            len,
+            shared.len() + subjects_only.len(),
+            predicates.len(),
+            shared.len() + objects_only.len(),
+        );
+        ControlInfo::write(
+            &mut writer,
+            Component::Header,
+            HEADER_NTRIPLES,
+            &[("length", header.len() as u64)],
+        )?;
+        writer.write_all(header.as_bytes())?;
+
+        // The terms that are both subjects and objects go first, followed by
+        // the rest of the subjects, the predicates and the rest of the objects
+        let sections = [
+            Section::new(&shared),
+            Section::new(&subjects_only),
+            Section::new(&predicates),
+            Section::new(&objects_only),
+        ];
+        ControlInfo::write(
+            &mut writer,
+            Component::Dictionary,
+            DICTIONARY_FOUR,
+            &[
+                ("mapping", 1),
+                ("sizeStrings", sections.iter().map(Section::size).sum()),
+            ],
+        )?;
+        for section in sections.iter() {
+            section.write(&mut writer)?;
+        }
+
+        let mut sorter = ExternalSorter::new(capacity, |a: &EncodedTriple, b| a.cmp(b), true);
+        triples(&mut |subject, predicate, object| {
+            Ok(sorter.push((
+                subject_ids[&subject],
+                predicate_ids[&predicate],
+                object_ids[&object],
+            ))?)
+        })?;
+        let mut encoded = sorter.finish()?;
+        BitmapTriples::write(
+            &mut writer,
+            encoded.by_ref().map(|(subject, predicate, object)| {
+                (subject as u64, predicate as u64, object as u64)
+            }),
+            predicates.len() as u64,
+            (shared.len() + objects_only.len()) as u64,
+        )?;
+        if let Some(err) = encoded.error() {
+            return Err(err.into());
+        }
+        writer.flush()?;
+
+        Ok(writer)
+    }
+}
+
+struct HdtFile {
+    reference_system: ReferenceSystem,
+    dictionary: Dictionary,
+    // The index in the Dictionary of every HDT identifier, minus one
+    subjects: Vec<u32>,
+    predicates: Vec<u32>,
+    objects: Vec<u32>,
+    triples: BitmapTriples,
+}
+
+impl HdtFile {
+    fn read(
+        reader: &mut impl Read,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+    ) -> HdtResult<Self> {
+        let global = ControlInfo::read(reader, Component::Global)?;
+        if global.format != HDT_V1 {
+            return Err(ParserError::Hdt(format!(
+                "unsupported version {}",
+                global.format
+            )));
+        }
+
+        // The header holds the metadata of the dataset, which is not needed
+        let header = ControlInfo::read(reader, Component::Header)?;
+        read_bytes(reader, header.property("length")? as usize)?;
+
+        let control_info = ControlInfo::read(reader, Component::Dictionary)?;
+        if control_info.format != DICTIONARY_FOUR {
+            return Err(ParserError::Hdt(format!(
+                "unsupported dictionary {}",
+                control_info.format
+            )));
+        }
+        // The terms that are both subjects and objects are kept in a section
+        // of their own, whose identifiers precede the ones of both sections
+        // The blank nodes are relabeled in the order of the sections, which
+        // are sorted again when the Dictionary is created
+        let mut blank_nodes = options.blank_nodes();
+        let mut relabel = |terms: Vec<String>| {
+            terms
+                .into_iter()
+                .map(|term| blank_nodes.relabel(term))
+                .collect::<Vec<_>>()
+        };
+        let shared = relabel(read_section(reader)?);
+        let subjects = relabel(read_section(reader)?);
+        let predicates = read_section(reader)?;
+        let objects = relabel(read_section(reader)?);
+
+        let dictionary = Dictionary::from_terms(
+            reference_system.to_owned(),
+            options.dictionary_format,
+            sorted(shared.iter().chain(&subjects), str::cmp)?,
+            to_set(predicates.iter())?,
+            sorted(shared.iter().chain(&objects), cmp_terms)?,
+        )?;
+
+        Ok(HdtFile {
+            reference_system: reference_system.to_owned(),
+            subjects: shared
+                .iter()
+                .chain(&subjects)
+                .map(|term| dictionary.get_subject_idx_unchecked(term) as u32)
+                .collect(),
+            predicates: predicates
+                .iter()
+                .map(|term| dictionary.get_predicate_idx_unchecked(term) as u32)
+                .collect(),
+            objects: shared
+                .iter()
+                .chain(&objects)
+                .map(|term| dictionary.get_object_idx_unchecked(term) as u32)
+                .collect(),
+            dictionary,
+            triples: BitmapTriples::read(reader)?,
+        })
+    }
+
+    fn first_term_size(&self) -> usize {
+        match self.reference_system {
+            ReferenceSystem::SPO | ReferenceSystem::SOP => self.subjects.len(),
+            ReferenceSystem::PSO | ReferenceSystem::POS => self.predicates.len(),
+            ReferenceSystem::OSP | ReferenceSystem::OPS => self.objects.len(),
+        }
+    }
+
+    fn encoded_triples(&self) -> impl Iterator<Item = HdtResult<EncodedTriple>> + '_ {
+        self.triples.iter().map(|(subject, predicate, object)| {
+            Ok(order(
+                &self.reference_system,
+                locate(&self.subjects, subject)?,
+                locate(&self.predicates, predicate)?,
+                locate(&self.objects, object)?,
+            ))
+        })
+    }
+}
+
+fn locate(ids: &[u32], id: u64) -> HdtResult<u32> {
+    match (id as usize).checked_sub(1).and_then(|idx| ids.get(idx)) {
+        Some(&idx) => Ok(idx),
+        None => Err(ParserError::Hdt(format!("unknown identifier {}", id))),
+    }
+}
+
+fn to_set<'a>(terms: impl Iterator<Item = &'a String>) -> HdtResult<Set> {
+    match Set::new(sorted(terms, str::cmp)?) {
+        Ok(set) => Ok(set),
+        Err(err) => Err(ParserError::Dictionary(err.to_string())),
+    }
+}
+
+fn sorted<'a>(
+    terms: impl Iterator<Item = &'a String>,
+    compare: fn(&str, &str) -> Ordering,
+) -> HdtResult<Vec<&'a String>> {
+    let mut terms = terms.collect::<Vec<_>>();
+    if terms.is_empty() {
+        return Err(ParserError::EmptyGraph);
+    }
+    terms.sort_unstable_by(|a, b| compare(a, b));
+    Ok(terms)
+}
+
+/// The distinct terms among the provided indices, written as HDT keeps them,
+/// next to their index in the Dictionary
+fn used_terms(
+    indices: BTreeSet<usize>,
+    decode: impl FnOnce(Vec<usize>) -> Vec<StorageResult<Option<String>>>,
+) -> Result<Vec<(String, usize)>, RemoteHDTError> {
+    let indices = indices.into_iter().collect::<Vec<_>>();
+
+    decode(indices.clone())
+        .into_iter()
+        .zip(indices)
+        .map(|(term, index)| match term? {
+            Some(term) => Ok((to_hdt(&term)?, index)),
+            None => Err(OpsError::TripleNotDecoded.into()),
+        })
+        .collect()
+}
+
+/// The metadata of the dataset, which follows the VoID vocabulary
+fn header(triples: usize, subjects: usize, predicates: usize, objects: usize) -> String {
+    [
+        (
+            "<http://www.w3.org/1999/02/22-rdf-syntax-ns#type>",
+            String::from("<http://purl.org/HDT/hdt#Dataset>"),
+        ),
+        (
+            "<http://rdfs.org/ns/void#triples>",
+            format!("\"{}\"", triples),
+        ),
+        (
+            "<http://rdfs.org/ns/void#distinctSubjects>",
+            format!("\"{}\"", subjects),
+        ),
+        (
+            "<http://rdfs.org/ns/void#properties>",
+            format!("\"{}\"", predicates),
+        ),
+        (
+            "<http://rdfs.org/ns/void#distinctObjects>",
+            format!("\"{}\"", objects),
+        ),
+    ]
+    .iter()
+    .map(|(predicate, object)| format!("_:dataset {} {} .\n", predicate, object))
+    .collect()
+}
@@ -0,0 +1,133 @@
+use std::io::Read;
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use crate::error::ParserError;
+use crate::storage::params::ReferenceSystem;
+
+use super::control::Component;
+use super::control::ControlInfo;
+use super::encoding::Bitmap;
+use super::encoding::LogSequence;
+use super::encoding::Packed;
+use super::HdtResult;
+
+const TRIPLES_BITMAP: &str = "<http://purl.org/HDT/hdt#triplesBitmap>";
+
+/// The triples of an HDT file, sorted following the order in which they were
+/// written. The first terms are implicit, as every group of second terms
+/// belongs to the next one, while the bitmaps mark the last second term of
+/// each first term, and the last third term of each pair.
+pub(super) struct BitmapTriples {
+    order: ReferenceSystem,
+    bitmap_y: Bitmap,
+    bitmap_z: Bitmap,
+    sequence_y: LogSequence,
+    sequence_z: LogSequence,
+}
+
+impl BitmapTriples {
+    pub(super) fn read(reader: &mut impl Read) -> HdtResult<Self> {
+        let control_info = ControlInfo::read(reader, Component::Triples)?;
+        if control_info.format != TRIPLES_BITMAP {
+            return Err(ParserError::Hdt(format!(
+                "unsupported triples {}",
+                control_info.format
+            )));
+        }
+        let order = match control_info.property("order")? {
+            1 => ReferenceSystem::SPO,
+            2 => ReferenceSystem::SOP,
+            3 => ReferenceSystem::PSO,
+            4 => ReferenceSystem::POS,
+            5 => ReferenceSystem::OSP,
+            6 => ReferenceSystem::OPS,
+            _ => return Err(ParserError::Hdt(String::from("unknown triples order"))),
+        };
+
+        let triples = BitmapTriples {
+            order,
+            bitmap_y: Bitmap::read(reader)?,
+            bitmap_z: Bitmap::read(reader)?,
+            sequence_y: LogSequence::read(reader)?,
+            sequence_z: LogSequence::read(reader)?,
+        };
+
+        match (
+            triples.bitmap_y.len() == triples.sequence_y.len(),
+            triples.bitmap_z.len() == triples.sequence_z.len(),
+        ) {
+            (true, true) => Ok(triples),
+            _ => Err(ParserError::Hdt(String::from(
+                "the bitmaps do not match the sequences",
+            ))),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.sequence_z.len()
+    }
+
+    /// Iterates over the triples, whose terms are given by their HDT
+    /// identifiers, which start from 1, sorted as subject, predicate and
+    /// object
+    pub(super) fn iter(&self) -> impl Iterator<Item = (u64, u64, u64)> + '_ {
+        let (mut x, mut y) = (1, 0);
+        (0..self.len()).map_while(move |z| {
+            if y >= self.sequence_y.len() {
+                return None;
+            }
+            let (first, second, third) = (x, self.sequence_y.get(y), self.sequence_z.get(z));
+            if self.bitmap_z.get(z) {
+                if self.bitmap_y.get(y) {
+                    x += 1;
+                }
+                y += 1;
+            }
+
+            Some(self.order.to_spo(first, second, third))
+        })
+    }
+
+    /// Writes the triples, which are expected to be sorted and unique, in SPO
+    /// order; that is, grouped by subject, and then by predicate. The largest
+    /// identifiers of the predicates and the objects give the width of their
+    /// sequences, so that the triples are packed as they are visited.
+    pub(super) fn write(
+        writer: &mut impl Write,
+        triples: impl Iterator<Item = (u64, u64, u64)>,
+        predicates: u64,
+        objects: u64,
+    ) -> IoResult<()> {
+        ControlInfo::write(writer, Component::Triples, TRIPLES_BITMAP, &[("order", 1)])?;
+
+        let (mut bitmap_y, mut sequence_y) =
+            (Packed::new(1), Packed::new(Packed::width(predicates)));
+        let (mut bitmap_z, mut sequence_z) = (Packed::new(1), Packed::new(Packed::width(objects)));
+        let mut triples = triples.peekable();
+        let mut previous = None;
+        while let Some((subject, predicate, object)) = triples.next() {
+            let next = triples
+                .peek()
+                .map(|&(subject, predicate, _)| (subject, predicate));
+            if previous != Some((subject, predicate)) {
+                sequence_y.push(predicate);
+            }
+            sequence_z.push(object);
+            // The last object of the pair, which may also be the one of the
+            // last predicate of the subject
+            if next != Some((subject, predicate)) {
+                bitmap_z.push(1);
+                bitmap_y.push((next.map(|(subject, _)| subject) != Some(subject)) as u64);
+            } else {
+                bitmap_z.push(0);
+            }
+            previous = Some((subject, predicate));
+        }
+
+        Bitmap::write_packed(writer, &bitmap_y)?;
+        Bitmap::write_packed(writer, &bitmap_z)?;
+        LogSequence::write_packed(writer, &sequence_y)?;
+        LogSequence::write_packed(writer, &sequence_z)
+    }
+}
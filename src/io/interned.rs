@@ -0,0 +1,156 @@
+use fcsd::Set;
+use std::collections::HashMap;
+
+use crate::dictionary::partition::cmp_terms;
+use crate::dictionary::Dictionary;
+use crate::error::ParserError;
+use crate::storage::params::DictionaryFormat;
+use crate::storage::params::ReferenceSystem;
+
+use super::external::EncodedTriple;
+use super::order;
+use super::Graph;
+use super::NamedGraphs;
+use super::RdfParserResult;
+
+/// The triples of an RDF dump whose terms are replaced by temporary
+/// identifiers, which are assigned in the order in which the terms are first
+/// seen. As such, the dump is read only once, while the final identifiers,
+/// which follow the order of the terms in the Dictionary, are assigned after
+/// all the terms are known. The triples belonging to a named graph are also
+/// kept, next to the index of the graph, so that they can be partitioned.
+#[derive(Default)]
+pub(crate) struct InternedGraph {
+    subjects: HashMap<String, u32>,
+    predicates: HashMap<String, u32>,
+    objects: HashMap<String, u32>,
+    graphs: HashMap<String, u32>,
+    triples: Vec<EncodedTriple>,
+    named_triples: Vec<(u32, usize)>,
+}
+
+impl InternedGraph {
+    pub(crate) fn push(
+        &mut self,
+        subject: &str,
+        predicate: &str,
+        object: &str,
+        graph_name: Option<&str>,
+    ) {
+        let triple = (
+            intern(&mut self.subjects, subject),
+            intern(&mut self.predicates, predicate),
+            intern(&mut self.objects, object),
+        );
+        if let Some(graph_name) = graph_name {
+            let graph = intern(&mut self.graphs, graph_name);
+            self.named_triples.push((graph, self.triples.len()));
+        }
+        self.triples.push(triple);
+    }
+
+    pub(crate) fn finish(
+        self,
+        reference_system: &ReferenceSystem,
+        dictionary_format: DictionaryFormat,
+    ) -> RdfParserResult {
+        if self.triples.is_empty() {
+            return Err(ParserError::EmptyGraph);
+        }
+
+        let subjects = sort_terms(self.subjects);
+        let predicates = sort_terms(self.predicates);
+        // The objects are sorted partition by partition instead
+        let mut objects = self.objects.into_iter().collect::<Vec<_>>();
+        objects.sort_unstable_by(|(a, _), (b, _)| cmp_terms(a, b));
+
+        let dictionary = Dictionary::from_terms(
+            reference_system.to_owned(),
+            dictionary_format,
+            subjects.iter().map(|(term, _)| term),
+            to_set(&predicates)?,
+            objects.iter().map(|(term, _)| term),
+        )?;
+
+        // Each of the terms is located only once in the Dictionary, instead of
+        // once for every triple in which it appears
+        let subject_ids = remap(&subjects, |term| dictionary.get_subject_idx_unchecked(term));
+        let predicate_ids = remap(&predicates, |term| {
+            dictionary.get_predicate_idx_unchecked(term)
+        });
+        let object_ids = remap(&objects, |term| dictionary.get_object_idx_unchecked(term));
+
+        let first_term_size = match reference_system {
+            ReferenceSystem::SPO | ReferenceSystem::SOP => subjects.len(),
+            ReferenceSystem::PSO | ReferenceSystem::POS => predicates.len(),
+            ReferenceSystem::OSP | ReferenceSystem::OPS => objects.len(),
+        };
+        let encode = |(sidx, pidx, oidx): EncodedTriple| {
+            order(
+                reference_system,
+                subject_ids[sidx as usize],
+                predicate_ids[pidx as usize],
+                object_ids[oidx as usize],
+            )
+        };
+
+        // The graphs are sorted by name, which is the order in which their
+        // partitions are stored
+        let graphs = sort_terms(self.graphs);
+        let graph_ids = remap(&graphs, |graph_name| {
+            graphs
+                .binary_search_by(|(term, _)| term.as_str().cmp(graph_name))
+                .unwrap()
+        });
+        let mut named_graphs: NamedGraphs = graphs
+            .into_iter()
+            .map(|(graph_name, _)| (graph_name, vec![Vec::new(); first_term_size]))
+            .collect();
+        self.named_triples.iter().for_each(|(graph, index)| {
+            let (first, second, third) = encode(self.triples[*index]);
+            named_graphs[graph_ids[*graph as usize] as usize].1[first as usize]
+                .push((second, third))
+        });
+
+        let mut graph: Graph = vec![Vec::new(); first_term_size];
+        self.triples.into_iter().for_each(|triple| {
+            let (first, second, third) = encode(triple);
+            graph[first as usize].push((second, third))
+        });
+
+        Ok((graph, dictionary, named_graphs))
+    }
+}
+
+fn intern(terms: &mut HashMap<String, u32>, term: &str) -> u32 {
+    match terms.get(term) {
+        Some(id) => *id,
+        None => {
+            let id = terms.len() as u32;
+            terms.insert(term.to_owned(), id);
+            id
+        }
+    }
+}
+
+fn sort_terms(terms: HashMap<String, u32>) -> Vec<(String, u32)> {
+    let mut terms = terms.into_iter().collect::<Vec<_>>();
+    terms.sort_unstable();
+    terms
+}
+
+fn to_set(terms: &[(String, u32)]) -> Result<Set, ParserError> {
+    match Set::new(terms.iter().map(|(term, _)| term)) {
+        Ok(set) => Ok(set),
+        Err(err) => Err(ParserError::Dictionary(err.to_string())),
+    }
+}
+
+/// Maps every temporary identifier to the index of its term in the Dictionary
+fn remap(terms: &[(String, u32)], locate: impl Fn(&str) -> usize) -> Vec<u32> {
+    let mut ids = vec![0; terms.len()];
+    terms
+        .iter()
+        .for_each(|(term, id)| ids[*id as usize] = locate(term) as u32);
+    ids
+}
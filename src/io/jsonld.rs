@@ -0,0 +1,1034 @@
+use rio_api::model::BlankNode;
+use rio_api::model::GraphName;
+use rio_api::model::Literal;
+use rio_api::model::NamedNode;
+use rio_api::model::Subject;
+use rio_api::model::Term;
+use rio_api::model::Triple;
+use rio_api::parser::LineBytePosition;
+use rio_api::parser::ParseError;
+use serde_json::Map;
+use serde_json::Number;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::BufRead;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::statements::StatementsParser;
+use super::Backend;
+use super::ParserOptions;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+/// Contexts may refer to other contexts; as such, the depth of the references
+/// is limited, so that cycles among them are detected
+const MAX_CONTEXT_DEPTH: usize = 16;
+
+pub struct JsonLd;
+
+impl Backend<JsonLdParser, JsonLdError> for JsonLd {
+    fn concrete_parser(reader: Box<dyn BufRead>, options: &ParserOptions) -> JsonLdParser {
+        JsonLdParser::new(reader, options.contexts, options.path)
+    }
+}
+
+/// JSON-LD documents cannot be parsed in a streaming manner, as the context and
+/// the identifier of a node may come after its properties; hence, the whole
+/// document is read and converted into statements before they are returned.
+/// This also holds when using [`crate::storage::params::MemoryStrategy::Streaming`],
+/// which reads the dump twice, so the document is converted twice as well.
+/// Remote contexts are never fetched, but read from their local copies. The
+/// features of JSON-LD 1.1 that would change the meaning of the terms, such
+/// as scoped or protected contexts, or id, type and graph maps, are not
+/// supported, and result in an error instead of in a different set of
+/// statements.
+pub(crate) struct JsonLdParser {
+    statements: std::vec::IntoIter<Statement>,
+    error: Option<JsonLdError>,
+}
+
+#[derive(Debug)]
+pub struct JsonLdError {
+    message: String,
+    position: Option<LineBytePosition>,
+}
+
+#[derive(Clone)]
+enum Node {
+    Iri(String),
+    BlankNode(String),
+}
+
+enum Object {
+    Node(Node),
+    Literal {
+        value: String,
+        language: Option<String>,
+        datatype: Option<String>,
+    },
+}
+
+struct Statement {
+    subject: Node,
+    predicate: String,
+    object: Object,
+    graph: Option<Node>,
+}
+
+#[derive(Clone, Default)]
+struct Context {
+    base: Option<String>,
+    vocab: Option<String>,
+    language: Option<String>,
+    terms: HashMap<String, Definition>,
+}
+
+#[derive(Clone, Default)]
+struct Definition {
+    /// The IRI of the term, which is `None` if the term is explicitly ignored
+    id: Option<String>,
+    /// Either `@id`, `@vocab`, `@json` or the IRI of a datatype
+    kind: Option<String>,
+    language: Option<Option<String>>,
+    container: Vec<String>,
+    reverse: bool,
+}
+
+/// Converts a JSON-LD document into RDF statements, by expanding the terms of
+/// the document using its contexts
+struct Converter<'a> {
+    contexts: &'a HashMap<String, PathBuf>,
+    /// The directory of the dump, if it is read from a file, against which the
+    /// relative references to the contexts are resolved
+    directory: Option<&'a Path>,
+    statements: Vec<Statement>,
+    blank_nodes: HashMap<String, String>,
+    next_blank_node: usize,
+}
+
+impl JsonLdParser {
+    fn new(
+        reader: Box<dyn BufRead>,
+        contexts: &HashMap<String, PathBuf>,
+        path: Option<&str>,
+    ) -> Self {
+        let mut converter = Converter {
+            contexts,
+            directory: path.and_then(|path| Path::new(path).parent()),
+            statements: Vec::new(),
+            blank_nodes: HashMap::new(),
+            next_blank_node: 0,
+        };
+        let result = match serde_json::from_reader(reader) {
+            Ok(document) => converter.document(&document),
+            Err(err) => Err(JsonLdError::from(err)),
+        };
+
+        JsonLdParser {
+            statements: converter.statements.into_iter(),
+            error: result.err(),
+        }
+    }
+}
+
+impl StatementsParser for JsonLdParser {
+    type Error = JsonLdError;
+
+    fn parse_step<E: From<Self::Error>>(
+        &mut self,
+        on_statement: &mut impl FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        if let Some(error) = self.error.take() {
+            return Err(error.into());
+        }
+
+        match self.statements.next() {
+            Some(statement) => on_statement(
+                Triple {
+                    subject: statement.subject.as_subject(),
+                    predicate: NamedNode {
+                        iri: &statement.predicate,
+                    },
+                    object: statement.object.as_term(),
+                },
+                statement.graph.as_ref().map(Node::as_graph_name),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn is_end(&self) -> bool {
+        self.error.is_none() && self.statements.len() == 0
+    }
+}
+
+impl<'a> Converter<'a> {
+    fn document(&mut self, document: &Value) -> Result<(), JsonLdError> {
+        let context = Context::default();
+        match document {
+            Value::Array(nodes) => nodes
+                .iter()
+                .try_for_each(|node| self.values(&context, None, node, None).map(|_| ())),
+            Value::Object(node) => self.node(&context, node, None).map(|_| ()),
+            _ => Err(JsonLdError::new(
+                "The document is neither a JSON object nor an array",
+            )),
+        }
+    }
+
+    /// Converts the properties of a node object into statements, returning the
+    /// node that is described by them
+    fn node(
+        &mut self,
+        active: &Context,
+        node: &Map<String, Value>,
+        graph: Option<&Node>,
+    ) -> Result<Option<Node>, JsonLdError> {
+        let context = match node.get("@context") {
+            Some(local) => self.process_context(active, local, 0)?,
+            None => active.to_owned(),
+        };
+        let members = entries(&context, node);
+
+        let subject = match members.iter().find(|(key, _, _)| key == "@id") {
+            Some((_, _, Value::String(id))) => expand_reference(&context, id, false)?,
+            _ => None,
+        };
+        // An object consisting of a graph alone is not a node by itself, but a
+        // container of the nodes of the graph in which it is
+        if subject.is_none() && members.iter().all(|(key, _, _)| key == "@graph") {
+            for (_, _, nodes) in members {
+                self.values(&context, None, nodes, graph)?;
+            }
+            return Ok(None);
+        }
+        let subject = match subject {
+            Some(iri) => self.node_ref(iri),
+            None => self.blank_node(),
+        };
+
+        self.properties(&context, &subject, members, graph)?;
+        Ok(Some(subject))
+    }
+
+    fn properties(
+        &mut self,
+        context: &Context,
+        subject: &Node,
+        members: Vec<(String, &str, &Value)>,
+        graph: Option<&Node>,
+    ) -> Result<(), JsonLdError> {
+        for (key, term, value) in members {
+            match key.as_str() {
+                "@type" => {
+                    for kind in as_array(value).filter_map(Value::as_str) {
+                        if let Some(kind) = expand_reference(context, kind, true)? {
+                            let object = Object::Node(self.node_ref(kind));
+                            self.emit(subject.to_owned(), RDF_TYPE, object, graph);
+                        }
+                    }
+                }
+                "@graph" => {
+                    self.values(context, None, value, Some(subject))?;
+                }
+                "@included" => {
+                    self.values(context, None, value, graph)?;
+                }
+                "@reverse" => {
+                    if let Value::Object(properties) = value {
+                        for (property, _, value) in entries(context, properties) {
+                            self.reverse(context, subject, &property, value, graph)?;
+                        }
+                    }
+                }
+                "@nest" => {
+                    for nested in as_array(value) {
+                        if let Value::Object(nested) = nested {
+                            let nested = entries(context, nested);
+                            self.properties(context, subject, nested, graph)?;
+                        }
+                    }
+                }
+                // The rest of the keywords do not result in any statement
+                keyword if keyword.starts_with('@') => {}
+                // Neither do the properties that are not mapped to an IRI
+                property if !is_absolute(property) => {}
+                property => {
+                    let definition = context.terms.get(term);
+                    if definition.is_some_and(|definition| definition.reverse) {
+                        self.reverse(context, subject, property, value, graph)?;
+                        continue;
+                    }
+                    for object in self.values(context, definition, value, graph)? {
+                        self.emit(subject.to_owned(), property, object, graph);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The values of reverse properties are the subjects of the statements,
+    /// whose object is the node being described
+    fn reverse(
+        &mut self,
+        context: &Context,
+        subject: &Node,
+        property: &str,
+        value: &Value,
+        graph: Option<&Node>,
+    ) -> Result<(), JsonLdError> {
+        if !is_absolute(property) {
+            return Ok(());
+        }
+        for object in self.values(context, None, value, graph)? {
+            if let Object::Node(node) = object {
+                self.emit(node, property, Object::Node(subject.to_owned()), graph);
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a value into the objects it represents, which may be several
+    /// of them if it is an array or a set
+    fn values(
+        &mut self,
+        context: &Context,
+        definition: Option<&Definition>,
+        value: &Value,
+        graph: Option<&Node>,
+    ) -> Result<Vec<Object>, JsonLdError> {
+        let kind = definition.and_then(|definition| definition.kind.as_deref());
+        let container = |name: &str| {
+            definition
+                .is_some_and(|definition| definition.container.iter().any(|item| item == name))
+        };
+
+        if kind == Some("@json") {
+            return Ok(vec![json_literal(value)]);
+        }
+        if container("@list") && !is_keyword_object(context, value, "@list") {
+            // The items are coerced as the values of the term, but they are not
+            // lists by themselves
+            let item = definition.map(|definition| Definition {
+                container: Vec::new(),
+                ..definition.to_owned()
+            });
+            let items = self.items(context, item.as_ref(), value, graph)?;
+            return Ok(vec![self.list(items, graph)]);
+        }
+
+        match value {
+            Value::Null => Ok(Vec::new()),
+            Value::Bool(value) => Ok(vec![literal(value.to_string(), None, Some(XSD_BOOLEAN))]),
+            Value::Number(number) => Ok(vec![number_literal(number, kind)]),
+            Value::String(value) => Ok(self
+                .string(context, definition, value)?
+                .into_iter()
+                .collect()),
+            Value::Array(_) => self.items(context, definition, value, graph),
+            Value::Object(map) if container("@language") => Ok(language_map(map)),
+            Value::Object(map) if container("@index") => {
+                let mut objects = Vec::new();
+                for value in map.values() {
+                    objects.extend(self.values(context, definition, value, graph)?);
+                }
+                Ok(objects)
+            }
+            Value::Object(map) => {
+                let members = entries(context, map);
+                let keyword = |name: &str| {
+                    members
+                        .iter()
+                        .find(|(key, _, _)| key == name)
+                        .map(|(_, _, value)| *value)
+                };
+                if let Some(value) = keyword("@value") {
+                    return Ok(value_object(
+                        context,
+                        value,
+                        keyword("@type"),
+                        keyword("@language"),
+                    )?
+                    .into_iter()
+                    .collect());
+                }
+                if let Some(items) = keyword("@list") {
+                    let items = self.items(context, None, items, graph)?;
+                    return Ok(vec![self.list(items, graph)]);
+                }
+                if let Some(items) = keyword("@set") {
+                    return self.items(context, None, items, graph);
+                }
+                Ok(self
+                    .node(context, map, graph)?
+                    .map(Object::Node)
+                    .into_iter()
+                    .collect())
+            }
+        }
+    }
+
+    fn items(
+        &mut self,
+        context: &Context,
+        definition: Option<&Definition>,
+        items: &Value,
+        graph: Option<&Node>,
+    ) -> Result<Vec<Object>, JsonLdError> {
+        let mut objects = Vec::new();
+        for item in as_array(items) {
+            objects.extend(self.values(context, definition, item, graph)?);
+        }
+        Ok(objects)
+    }
+
+    fn string(
+        &mut self,
+        context: &Context,
+        definition: Option<&Definition>,
+        value: &str,
+    ) -> Result<Option<Object>, JsonLdError> {
+        let kind = definition.and_then(|definition| definition.kind.as_deref());
+        Ok(match kind {
+            Some("@id") => {
+                expand_reference(context, value, false)?.map(|iri| Object::Node(self.node_ref(iri)))
+            }
+            Some("@vocab") => {
+                expand_reference(context, value, true)?.map(|iri| Object::Node(self.node_ref(iri)))
+            }
+            Some(datatype) if datatype != "@none" => {
+                Some(literal(value.to_string(), None, Some(datatype)))
+            }
+            _ => {
+                let language = match definition.and_then(|definition| definition.language.as_ref())
+                {
+                    Some(language) => language.to_owned(),
+                    None => context.language.to_owned(),
+                };
+                Some(Object::Literal {
+                    value: value.to_string(),
+                    language,
+                    datatype: None,
+                })
+            }
+        })
+    }
+
+    /// Converts the items into an RDF collection, returning its head
+    fn list(&mut self, items: Vec<Object>, graph: Option<&Node>) -> Object {
+        if items.is_empty() {
+            return Object::Node(Node::Iri(RDF_NIL.to_string()));
+        }
+
+        let head = self.blank_node();
+        let mut current = head.to_owned();
+        let last = items.len() - 1;
+        for (index, item) in items.into_iter().enumerate() {
+            let rest = match index == last {
+                true => Node::Iri(RDF_NIL.to_string()),
+                false => self.blank_node(),
+            };
+            self.emit(current.to_owned(), RDF_FIRST, item, graph);
+            self.emit(current, RDF_REST, Object::Node(rest.to_owned()), graph);
+            current = rest;
+        }
+
+        Object::Node(head)
+    }
+
+    fn emit(&mut self, subject: Node, predicate: &str, object: Object, graph: Option<&Node>) {
+        // Blank nodes cannot be used as predicates in RDF
+        if predicate.starts_with("_:") {
+            return;
+        }
+        self.statements.push(Statement {
+            subject,
+            predicate: predicate.to_string(),
+            object,
+            graph: graph.cloned(),
+        })
+    }
+
+    /// The blank nodes of the document are relabeled, so that their labels do
+    /// not collide with the ones that are generated
+    fn node_ref(&mut self, iri: String) -> Node {
+        match iri.strip_prefix("_:") {
+            Some(label) => match self.blank_nodes.get(label) {
+                Some(id) => Node::BlankNode(id.to_owned()),
+                None => {
+                    let node = self.blank_node();
+                    if let Node::BlankNode(id) = &node {
+                        self.blank_nodes.insert(label.to_string(), id.to_owned());
+                    }
+                    node
+                }
+            },
+            None => Node::Iri(iri),
+        }
+    }
+
+    fn blank_node(&mut self) -> Node {
+        self.next_blank_node += 1;
+        Node::BlankNode(format!("b{}", self.next_blank_node))
+    }
+
+    fn process_context(
+        &self,
+        active: &Context,
+        local: &Value,
+        depth: usize,
+    ) -> Result<Context, JsonLdError> {
+        if depth > MAX_CONTEXT_DEPTH {
+            return Err(JsonLdError::new(
+                "The contexts are nested too deeply, which may be due to a cycle",
+            ));
+        }
+
+        match local {
+            Value::Null => Ok(Context {
+                base: active.base.to_owned(),
+                ..Default::default()
+            }),
+            Value::Array(contexts) => contexts
+                .iter()
+                .try_fold(active.to_owned(), |context, local| {
+                    self.process_context(&context, local, depth + 1)
+                }),
+            Value::String(iri) => {
+                let document = self.load_context(iri)?;
+                match document.get("@context") {
+                    Some(local) => self.process_context(active, local, depth + 1),
+                    None => Err(JsonLdError::new(format!(
+                        "The context {} does not contain any @context",
+                        iri
+                    ))),
+                }
+            }
+            Value::Object(definitions) => {
+                if let Some(keyword) = ["@protected", "@propagate"]
+                    .into_iter()
+                    .find(|keyword| definitions.contains_key(*keyword))
+                {
+                    return Err(JsonLdError::new(format!(
+                        "The keyword {} of the contexts is not supported",
+                        keyword
+                    )));
+                }
+                let mut context = match definitions.get("@import") {
+                    Some(Value::String(iri)) => match self.load_context(iri)?.get("@context") {
+                        Some(imported) => self.process_context(active, imported, depth + 1)?,
+                        None => active.to_owned(),
+                    },
+                    _ => active.to_owned(),
+                };
+                match definitions.get("@base") {
+                    Some(Value::String(base)) => {
+                        context.base = Some(resolve(context.base.as_deref(), base))
+                    }
+                    Some(Value::Null) => context.base = None,
+                    _ => {}
+                }
+                match definitions.get("@vocab") {
+                    Some(Value::String(vocab)) => {
+                        context.vocab = expand_reference(&context, vocab, true)?
+                    }
+                    Some(Value::Null) => context.vocab = None,
+                    _ => {}
+                }
+                match definitions.get("@language") {
+                    Some(Value::String(language)) => {
+                        context.language = Some(language.to_lowercase())
+                    }
+                    Some(Value::Null) => context.language = None,
+                    _ => {}
+                }
+
+                let mut defined = HashMap::new();
+                for term in definitions.keys().filter(|term| !term.starts_with('@')) {
+                    define(&mut context, definitions, term, &mut defined)?;
+                }
+
+                Ok(context)
+            }
+            _ => Err(JsonLdError::new("The context is not a JSON object")),
+        }
+    }
+
+    /// Reads a context from its local copy. Apart from the copies that have
+    /// been provided, only the contexts referenced relative to the dump are
+    /// read, from within its directory; hence, documents cannot make any other
+    /// file be read, such as the ones referenced by `file:` IRIs.
+    fn load_context(&self, iri: &str) -> Result<Value, JsonLdError> {
+        let path = match (self.contexts.get(iri), iri.split_once(':')) {
+            (Some(path), _) => path.to_owned(),
+            (None, Some(("file", _))) => {
+                return Err(JsonLdError::new(format!(
+                    "The local context {} is only read if its path is provided",
+                    iri
+                )))
+            }
+            (None, Some(_)) => {
+                return Err(JsonLdError::new(format!(
+                    "The remote context {} cannot be fetched; a local copy of it has to be provided",
+                    iri
+                )))
+            }
+            (None, None) => match self.directory {
+                Some(directory) if is_contained(iri) => directory.join(iri),
+                Some(_) => {
+                    return Err(JsonLdError::new(format!(
+                        "The context {} is not within the directory of the dump",
+                        iri
+                    )))
+                }
+                None => {
+                    return Err(JsonLdError::new(format!(
+                        "The relative context {} cannot be resolved, as the dump is not read from a file",
+                        iri
+                    )))
+                }
+            },
+        };
+
+        let content = match fs::read(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                return Err(JsonLdError::new(format!(
+                    "The context {} could not be read: {}",
+                    iri, err
+                )))
+            }
+        };
+        match serde_json::from_slice(&content) {
+            Ok(context) => Ok(context),
+            Err(err) => Err(JsonLdError::new(format!(
+                "The context {} is not valid JSON: {}",
+                iri, err
+            ))),
+        }
+    }
+}
+
+/// Creates the definition of a term, after the ones of the terms it depends on
+fn define(
+    context: &mut Context,
+    definitions: &Map<String, Value>,
+    term: &str,
+    defined: &mut HashMap<String, bool>,
+) -> Result<(), JsonLdError> {
+    match defined.get(term) {
+        Some(true) => return Ok(()),
+        Some(false) => {
+            return Err(JsonLdError::new(format!(
+                "The definition of the term {} is cyclic",
+                term
+            )))
+        }
+        None => {
+            defined.insert(term.to_string(), false);
+        }
+    }
+
+    let mut definition = Definition::default();
+    let id = match &definitions[term] {
+        Value::Null => None,
+        Value::String(id) => Some(id.as_str()),
+        Value::Object(map) => {
+            if let Some(keyword) = ["@context", "@protected", "@prefix"]
+                .into_iter()
+                .find(|keyword| map.contains_key(*keyword))
+            {
+                return Err(JsonLdError::new(format!(
+                    "The keyword {} of the definition of the term {} is not supported",
+                    keyword, term
+                )));
+            }
+            if let Some(Value::String(kind)) = map.get("@type") {
+                define_dependency(context, definitions, term, kind, defined)?;
+                definition.kind = match kind.as_str() {
+                    "@id" | "@vocab" | "@json" | "@none" => Some(kind.to_owned()),
+                    kind => expand_iri(context, kind, true, false),
+                };
+            }
+            match map.get("@language") {
+                Some(Value::String(language)) => {
+                    definition.language = Some(Some(language.to_lowercase()))
+                }
+                Some(Value::Null) => definition.language = Some(None),
+                _ => {}
+            }
+            definition.container = as_array(map.get("@container").unwrap_or(&Value::Null))
+                .filter_map(|container| container.as_str().map(str::to_string))
+                .collect();
+            // The keys of these maps are identifiers, types or graphs of their
+            // values; as such, they would be taken for properties otherwise
+            if let Some(container) = definition
+                .container
+                .iter()
+                .find(|container| ["@id", "@type", "@graph"].contains(&container.as_str()))
+            {
+                return Err(JsonLdError::new(format!(
+                    "The container {} of the term {} is not supported",
+                    container, term
+                )));
+            }
+            match (map.get("@reverse"), map.get("@id")) {
+                (Some(Value::String(id)), _) => {
+                    definition.reverse = true;
+                    Some(id.as_str())
+                }
+                (_, Some(Value::String(id))) => Some(id.as_str()),
+                (_, Some(Value::Null)) => None,
+                _ => Some(term),
+            }
+        }
+        _ => {
+            return Err(JsonLdError::new(format!(
+                "The definition of the term {} is invalid",
+                term
+            )))
+        }
+    };
+
+    definition.id = match id {
+        Some(id) => {
+            define_dependency(context, definitions, term, id, defined)?;
+            match id == term && !term.contains(':') {
+                // A term that is not mapped to any IRI is appended to the
+                // vocabulary, if there is such
+                true => context
+                    .vocab
+                    .as_ref()
+                    .map(|vocab| format!("{}{}", vocab, term)),
+                false => expand_iri(context, id, true, false),
+            }
+        }
+        None => None,
+    };
+    context.terms.insert(term.to_string(), definition);
+    defined.insert(term.to_string(), true);
+
+    Ok(())
+}
+
+/// Defines the term or the prefix used by the IRI of a term, if it is defined
+/// in the same context
+fn define_dependency(
+    context: &mut Context,
+    definitions: &Map<String, Value>,
+    term: &str,
+    iri: &str,
+    defined: &mut HashMap<String, bool>,
+) -> Result<(), JsonLdError> {
+    let dependency = match iri.split_once(':') {
+        Some((prefix, _)) => prefix,
+        None => iri,
+    };
+    match dependency != term && definitions.contains_key(dependency) {
+        true => define(context, definitions, dependency, defined),
+        false => Ok(()),
+    }
+}
+
+/// Expands a term, a compact IRI or a relative IRI into an absolute IRI; if the
+/// term is explicitly ignored by the context, `None` is returned
+fn expand_iri(context: &Context, value: &str, vocab: bool, relative: bool) -> Option<String> {
+    if value.starts_with('@') {
+        return Some(value.to_string());
+    }
+    if vocab {
+        if let Some(definition) = context.terms.get(value) {
+            return definition.id.to_owned();
+        }
+    }
+    if let Some((prefix, suffix)) = value.split_once(':') {
+        if prefix == "_" || suffix.starts_with("//") {
+            return Some(value.to_string());
+        }
+        return match context.terms.get(prefix) {
+            Some(Definition { id: Some(id), .. }) => Some(format!("{}{}", id, suffix)),
+            _ => Some(value.to_string()),
+        };
+    }
+    match (&context.vocab, vocab, relative) {
+        (Some(vocab), true, _) => Some(format!("{}{}", vocab, value)),
+        (_, _, true) => Some(resolve(context.base.as_deref(), value)),
+        _ => Some(value.to_string()),
+    }
+}
+
+/// Expands an IRI that may be relative to the base IRI, which cannot be done
+/// if there is no base IRI, as a relative IRI is not a valid RDF term
+fn expand_reference(
+    context: &Context,
+    value: &str,
+    vocab: bool,
+) -> Result<Option<String>, JsonLdError> {
+    match expand_iri(context, value, vocab, true) {
+        Some(iri) if !iri.starts_with('@') && !iri.contains(':') => Err(JsonLdError::new(format!(
+            "The relative IRI {} cannot be resolved without a base IRI",
+            value
+        ))),
+        iri => Ok(iri),
+    }
+}
+
+/// Resolves a relative IRI against the base IRI, if there is such
+fn resolve(base: Option<&str>, reference: &str) -> String {
+    let base = match base {
+        Some(base) if !reference.contains(':') => base,
+        _ => return reference.to_string(),
+    };
+    let (scheme, rest) = match base.split_once(':') {
+        Some(parts) => parts,
+        None => return format!("{}{}", base, reference),
+    };
+    let without_fragment = base.split('#').next().unwrap_or(base);
+    let without_query = without_fragment
+        .split('?')
+        .next()
+        .unwrap_or(without_fragment);
+    let authority_end = match rest.strip_prefix("//") {
+        Some(authority) => {
+            scheme.len() + 3 + authority.find(['/', '?', '#']).unwrap_or(authority.len())
+        }
+        None => scheme.len() + 1,
+    };
+
+    match reference.chars().next() {
+        None => without_fragment.to_string(),
+        Some('#') => format!("{}{}", without_fragment, reference),
+        Some('?') => format!("{}{}", without_query, reference),
+        Some('/') if reference.starts_with("//") => format!("{}:{}", scheme, reference),
+        Some('/') => format!("{}{}", &base[..authority_end], reference),
+        _ => {
+            let directory = match without_query[authority_end..].rfind('/') {
+                Some(index) => &without_query[..authority_end + index + 1],
+                None => &without_query[..authority_end],
+            };
+            let mut segments = directory[authority_end..]
+                .split('/')
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            segments.pop();
+            if segments.is_empty() {
+                segments.push(String::new());
+            }
+            for segment in reference.split('/') {
+                match segment {
+                    "." => {}
+                    ".." => {
+                        if segments.len() > 1 {
+                            segments.pop();
+                        }
+                    }
+                    segment => segments.push(segment.to_string()),
+                }
+            }
+            format!("{}{}", &base[..authority_end], segments.join("/"))
+        }
+    }
+}
+
+/// Expands the keys of a JSON object, so that keywords can be aliased
+fn entries<'v>(
+    context: &Context,
+    map: &'v Map<String, Value>,
+) -> Vec<(String, &'v str, &'v Value)> {
+    map.iter()
+        .filter(|(key, _)| key.as_str() != "@context")
+        .filter_map(|(key, value)| {
+            expand_iri(context, key, true, false).map(|expanded| (expanded, key.as_str(), value))
+        })
+        .collect()
+}
+
+fn is_keyword_object(context: &Context, value: &Value, keyword: &str) -> bool {
+    match value {
+        Value::Object(map) => entries(context, map)
+            .iter()
+            .any(|(key, _, _)| key == keyword),
+        _ => false,
+    }
+}
+
+/// Whether a relative path stays within the directory it is resolved against
+fn is_contained(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+fn is_absolute(iri: &str) -> bool {
+    iri.contains(':') && !iri.starts_with("_:")
+}
+
+fn as_array(value: &Value) -> impl Iterator<Item = &Value> {
+    match value {
+        Value::Array(values) => values.iter().collect::<Vec<_>>().into_iter(),
+        Value::Null => Vec::new().into_iter(),
+        value => vec![value].into_iter(),
+    }
+}
+
+fn literal(value: String, language: Option<String>, datatype: Option<&str>) -> Object {
+    Object::Literal {
+        value,
+        language,
+        datatype: datatype.map(str::to_string),
+    }
+}
+
+/// Numbers without a fractional part are integers, unless they are too big,
+/// while the rest of them are doubles in their canonical form
+fn number_literal(number: &Number, kind: Option<&str>) -> Object {
+    let double = number.as_f64().unwrap_or_default();
+    let (value, datatype) = match (number.as_i64(), number.as_u64()) {
+        (Some(integer), _) if kind != Some(XSD_DOUBLE) => (integer.to_string(), XSD_INTEGER),
+        (_, Some(integer)) if kind != Some(XSD_DOUBLE) => (integer.to_string(), XSD_INTEGER),
+        _ if double.fract() == 0.0 && double.abs() < 1e21 && kind != Some(XSD_DOUBLE) => {
+            (format!("{:.0}", double), XSD_INTEGER)
+        }
+        _ => (format!("{:E}", double), XSD_DOUBLE),
+    };
+    match kind {
+        Some(kind) if !kind.starts_with('@') => literal(value, None, Some(kind)),
+        _ => literal(value, None, Some(datatype)),
+    }
+}
+
+fn json_literal(value: &Value) -> Object {
+    literal(value.to_string(), None, Some(RDF_JSON))
+}
+
+fn language_map(map: &Map<String, Value>) -> Vec<Object> {
+    map.iter()
+        .flat_map(|(language, values)| {
+            as_array(values).filter_map(move |value| {
+                let language = match language.as_str() {
+                    "@none" => None,
+                    language => Some(language.to_lowercase()),
+                };
+                value
+                    .as_str()
+                    .map(|value| literal(value.to_string(), language, None))
+            })
+        })
+        .collect()
+}
+
+fn value_object(
+    context: &Context,
+    value: &Value,
+    kind: Option<&Value>,
+    language: Option<&Value>,
+) -> Result<Option<Object>, JsonLdError> {
+    let kind = match kind.and_then(Value::as_str) {
+        Some("@json") => return Ok(Some(json_literal(value))),
+        Some(kind) => expand_reference(context, kind, true)?,
+        None => None,
+    };
+
+    let language = language.and_then(Value::as_str).map(str::to_lowercase);
+    Ok(match value {
+        Value::String(value) => Some(match kind {
+            Some(kind) => literal(value.to_owned(), None, Some(&kind)),
+            None => literal(value.to_owned(), language, None),
+        }),
+        Value::Number(number) => Some(number_literal(number, kind.as_deref())),
+        Value::Bool(value) => Some(literal(
+            value.to_string(),
+            None,
+            Some(kind.as_deref().unwrap_or(XSD_BOOLEAN)),
+        )),
+        _ => None,
+    })
+}
+
+impl Node {
+    fn as_subject(&self) -> Subject<'_> {
+        match self {
+            Node::Iri(iri) => Subject::NamedNode(NamedNode { iri }),
+            Node::BlankNode(id) => Subject::BlankNode(BlankNode { id }),
+        }
+    }
+
+    fn as_graph_name(&self) -> GraphName<'_> {
+        match self {
+            Node::Iri(iri) => GraphName::NamedNode(NamedNode { iri }),
+            Node::BlankNode(id) => GraphName::BlankNode(BlankNode { id }),
+        }
+    }
+}
+
+impl Object {
+    fn as_term(&self) -> Term<'_> {
+        match self {
+            Object::Node(Node::Iri(iri)) => Term::NamedNode(NamedNode { iri }),
+            Object::Node(Node::BlankNode(id)) => Term::BlankNode(BlankNode { id }),
+            Object::Literal {
+                value,
+                language: Some(language),
+                ..
+            } => Term::Literal(Literal::LanguageTaggedString { value, language }),
+            Object::Literal {
+                value,
+                datatype: Some(iri),
+                ..
+            } => Term::Literal(Literal::Typed {
+                value,
+                datatype: NamedNode { iri },
+            }),
+            Object::Literal { value, .. } => Term::Literal(Literal::Simple { value }),
+        }
+    }
+}
+
+impl JsonLdError {
+    fn new(message: impl Into<String>) -> Self {
+        JsonLdError {
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonLdError {
+    fn from(err: serde_json::Error) -> Self {
+        JsonLdError {
+            message: err.to_string(),
+            position: match err.line() {
+                0 => None,
+                line => Some(LineBytePosition::new(line as u64, err.column() as u64)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for JsonLdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for JsonLdError {}
+
+impl ParseError for JsonLdError {
+    fn textual_position(&self) -> Option<LineBytePosition> {
+        self.position
+    }
+}
@@ -1,144 +1,529 @@
+use fcsd::Set;
+use rio_api::model::GraphName;
 use rio_api::model::Triple;
-use rio_api::parser::TriplesParser;
-use std::collections::HashSet;
+use rio_api::parser::ParseError;
+use std::collections::HashMap;
 use std::fs::File;
+use std::io::BufRead;
 use std::io::BufReader;
+use std::path::PathBuf;
 
+use crate::dictionary::partition::cmp_terms;
 use crate::dictionary::Dictionary;
+use crate::error::ParseReport;
 use crate::error::ParserError;
+use crate::error::SyntaxError;
+use crate::storage::params::BlankNodePolicy;
+use crate::storage::params::DictionaryFormat;
+use crate::storage::params::ErrorStrategy;
+use crate::storage::params::GraphPolicy;
+use crate::storage::params::RdfFormat;
 use crate::storage::params::ReferenceSystem;
 
+use self::blank_nodes::BlankNodes;
+use self::compression::decompress;
+use self::external::EncodedQuad;
+use self::external::EncodedTriple;
+use self::external::ExternalSorter;
+use self::external::Merge;
+use self::format::guess_format;
+use self::hdt::Hdt;
+use self::interned::InternedGraph;
+use self::jsonld::JsonLd;
+use self::nquads::NQuads;
 use self::ntriples::NTriples;
 use self::rdf_xml::RdfXml;
+use self::statements::StatementsParser;
+use self::trig::TriG;
 use self::turtle::Turtle;
 
+pub(crate) mod blank_nodes;
+mod compression;
+pub(crate) mod external;
+mod format;
+pub(crate) mod formatter;
+pub(crate) mod hdt;
+mod interned;
+mod jsonld;
+mod nquads;
 mod ntriples;
 mod rdf_xml;
+mod statements;
+mod trig;
 mod turtle;
 
-pub type RdfParserResult = Result<(Graph, Dictionary), ParserError>;
+pub type RdfParserResult = Result<(Graph, Dictionary, NamedGraphs), ParserError>;
 pub type Graph = Vec<Vec<(u32, u32)>>;
+/// The triples of each of the named graphs, sorted by the name of the graph
+pub type NamedGraphs = Vec<(String, Graph)>;
+/// A triple whose terms are written as in N-Triples; that is, IRIs are
+/// enclosed in angle brackets, and literals in double quotes
+pub type StringTriple = (String, String, String);
+pub(crate) type RdfStreamResult = Result<
+    (
+        Merge<EncodedTriple>,
+        usize,
+        Dictionary,
+        Option<NamedGraphStream>,
+    ),
+    ParserError,
+>;
 
-trait Backend<T: TriplesParser, E: From<<T>::Error>> {
-    fn parse(path: &str, reference_system: &ReferenceSystem) -> RdfParserResult {
-        // We create as many HashSets as fields we will be storing; that is, one
-        // for the subjects, another for the predicates, and one for the objects.
-        // The idea is that we will create a Dictionary matching every Term to
-        // an integer value; thus, we will be able to store the Triples in a
-        // more efficient manner
-        let mut subjects = HashSet::new();
-        let mut predicates = HashSet::new();
-        let mut objects = HashSet::new();
-
-        if let Err(err) = Self::parser_fn(path, &mut |triple: Triple| {
-            {
-                subjects.insert(triple.subject.to_string());
-                predicates.insert(triple.predicate.to_string());
-                objects.insert(triple.object.to_string());
-            };
+/// The settings that are shared by the parsers of all the formats
+pub(crate) struct ParserOptions<'a> {
+    pub(crate) graph_policy: GraphPolicy,
+    /// The local copies of the JSON-LD contexts, by their IRI
+    pub(crate) contexts: &'a HashMap<String, PathBuf>,
+    /// The path of the dump, if it is read from a file
+    pub(crate) path: Option<&'a str>,
+    pub(crate) dictionary_format: DictionaryFormat,
+    pub(crate) blank_node_policy: &'a BlankNodePolicy,
+    /// The scope by which the blank nodes are relabeled, which is empty if
+    /// they are kept as they are
+    pub(crate) scope: String,
+}
+
+impl ParserOptions<'_> {
+    fn blank_nodes(&self) -> BlankNodes<'_> {
+        BlankNodes::new(self.blank_node_policy, &self.scope)
+    }
+}
+
+/// The triples of the named graphs of a dump that is parsed in a streaming
+/// manner. They are sorted by graph, and then by their first term; hence, the
+/// triples of each graph are returned one after the other.
+pub(crate) struct NamedGraphStream {
+    pub(crate) names: Set,
+    pub(crate) sizes: Vec<usize>,
+    pub(crate) quads: Merge<EncodedQuad>,
+}
+
+trait Backend<T: StatementsParser, E: From<<T>::Error> + ParseError> {
+    fn parse(
+        path: &str,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+    ) -> RdfParserResult {
+        Self::parse_reader(open(path)?, reference_system, options, errors)
+    }
+
+    fn parse_streaming(
+        path: &str,
+        reference_system: &ReferenceSystem,
+        buffer_size: usize,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+    ) -> RdfStreamResult {
+        // The same two passes as in `parse` are performed, but neither the terms
+        // nor the triples are kept in memory. Instead, at most `buffer_size`
+        // elements are buffered, before they are sorted and spilled to disk
+        let mut subjects = ExternalSorter::new(buffer_size, String::cmp, true);
+        let mut predicates = ExternalSorter::new(buffer_size, String::cmp, true);
+        // The objects are sorted partition by partition, so that they are split
+        // into partitions as they are merged
+        let mut objects =
+            ExternalSorter::new(buffer_size, |a: &String, b: &String| cmp_terms(a, b), true);
+        let mut graphs = ExternalSorter::new(buffer_size, String::cmp, true);
+        // Errors while spilling are kept aside, as the ones returned by the
+        // callback are considered syntax errors, and hence, skipped
+        let mut spill_error = None;
+        // Both passes relabel the blank nodes in the same order, so the labels
+        // given to them in the first one are the same as in the second one
+        let mut blank_nodes = options.blank_nodes();
+
+        Self::parser_fn(open(path)?, options, errors, &mut |triple: Triple,
+                                                            graph_name: Option<
+            GraphName,
+        >| {
+            let mut pushed = subjects
+                .push(blank_nodes.relabel(triple.subject.to_string()))
+                .and(predicates.push(triple.predicate.to_string()))
+                .and(objects.push(blank_nodes.relabel(triple.object.to_string())));
+            if let Some(graph_name) = graph_name.filter(|_| options.graph_policy.is_keep()) {
+                pushed = pushed.and(graphs.push(blank_nodes.relabel(graph_name.to_string())));
+            }
+            if let Err(err) = pushed {
+                spill_error.get_or_insert(err);
+            }
             Ok(())
-        } as Result<(), E>)
-        {
-            return Err(ParserError::Dictionary(err));
         }
+            as Result<(), E>)?;
 
-        let mut graph = vec![
-            Vec::new();
-            match reference_system {
-                ReferenceSystem::SPO | ReferenceSystem::SOP => subjects.len(),
-                ReferenceSystem::PSO | ReferenceSystem::POS => predicates.len(),
-                ReferenceSystem::OSP | ReferenceSystem::OPS => objects.len(),
-            }
-        ];
-        let dictionary =
-            Dictionary::from_set_terms(reference_system.to_owned(), subjects, predicates, objects);
-
-        if let Err(err) = Self::parser_fn(path, &mut |triple: Triple| {
-            {
-                let sidx = dictionary.get_subject_idx_unchecked(&triple.subject.to_string());
-                let pidx = dictionary.get_predicate_idx_unchecked(&triple.predicate.to_string());
-                let oidx = dictionary.get_object_idx_unchecked(&triple.object.to_string());
-
-                match reference_system {
-                    ReferenceSystem::SPO => {
-                        if let Some(subject) = graph.get_mut(sidx) {
-                            subject.push((pidx as u32, oidx as u32))
-                        }
-                    }
-                    ReferenceSystem::SOP => {
-                        if let Some(subject) = graph.get_mut(sidx) {
-                            subject.push((oidx as u32, pidx as u32))
-                        }
-                    }
-                    ReferenceSystem::PSO => {
-                        if let Some(predicate) = graph.get_mut(pidx) {
-                            predicate.push((sidx as u32, oidx as u32))
-                        }
-                    }
-                    ReferenceSystem::POS => {
-                        if let Some(predicate) = graph.get_mut(pidx) {
-                            predicate.push((oidx as u32, sidx as u32))
-                        }
-                    }
-                    ReferenceSystem::OPS => {
-                        if let Some(object) = graph.get_mut(oidx) {
-                            object.push((pidx as u32, sidx as u32))
-                        }
-                    }
-                    ReferenceSystem::OSP => {
-                        if let Some(object) = graph.get_mut(oidx) {
-                            object.push((sidx as u32, pidx as u32))
-                        }
+        if let Some(err) = spill_error.take() {
+            return Err(ParserError::Dictionary(err.to_string()));
+        }
+        if subjects.len() == 0 {
+            return Err(ParserError::EmptyGraph);
+        }
+
+        // The merged runs are already sorted and unique, so the front-coded
+        // sets can be built from them without collecting the terms first
+        let (mut subjects, mut objects) = (merge(subjects)?, merge(objects)?);
+        let dictionary = Dictionary::from_terms(
+            reference_system.to_owned(),
+            options.dictionary_format,
+            subjects.by_ref(),
+            merge_to_set(predicates)?,
+            objects.by_ref(),
+        );
+        check_merge(&mut subjects)?;
+        check_merge(&mut objects)?;
+        let dictionary = dictionary?;
+        let graphs = match graphs.len() {
+            0 => None,
+            _ => Some(merge_to_set(graphs)?),
+        };
+
+        // Triples are sorted by their first term, which is the order in which
+        // they are written into the array, shard by shard
+        let mut triples = ExternalSorter::new(
+            buffer_size,
+            |a: &EncodedTriple, b: &EncodedTriple| a.0.cmp(&b.0),
+            false,
+        );
+        // The ones in named graphs are also sorted by their graph beforehand
+        let mut quads = ExternalSorter::new(
+            buffer_size,
+            |a: &EncodedQuad, b: &EncodedQuad| (a.0, a.1).cmp(&(b.0, b.1)),
+            false,
+        );
+        let mut sizes = vec![0; graphs.as_ref().map_or(0, |graphs| graphs.len())];
+
+        // The syntax errors have already been handled in the first pass; hence,
+        // the same ones are ignored now
+        let mut ignored = ErrorHandler::new(ErrorStrategy::Lenient(0));
+        let mut blank_nodes = options.blank_nodes();
+        Self::parser_fn(
+            open(path)?,
+            options,
+            &mut ignored,
+            &mut |triple: Triple, graph_name: Option<GraphName>| {
+                let (first, second, third) = encode(
+                    &dictionary,
+                    reference_system,
+                    &blank_nodes.relabel(triple.subject.to_string()),
+                    &triple.predicate.to_string(),
+                    &blank_nodes.relabel(triple.object.to_string()),
+                );
+                let mut pushed = triples.push((first, second, third));
+                if let (Some(graph_name), Some(graphs)) = (graph_name, graphs.as_ref()) {
+                    let graph_name = blank_nodes.relabel(graph_name.to_string());
+                    if let Some(graph) = graphs.locator().run(graph_name) {
+                        sizes[graph] += 1;
+                        pushed = pushed.and(quads.push((graph as u32, first, second, third)));
                     }
                 }
-            };
+                if let Err(err) = pushed {
+                    spill_error.get_or_insert(err);
+                }
+                Ok(())
+            } as Result<(), E>,
+        )?;
+
+        if let Some(err) = spill_error {
+            return Err(ParserError::Graph(err.to_string()));
+        }
+
+        let named_graphs = match graphs {
+            Some(names) => match quads.finish() {
+                Ok(quads) => Some(NamedGraphStream {
+                    names,
+                    sizes,
+                    quads,
+                }),
+                Err(err) => return Err(ParserError::Graph(err.to_string())),
+            },
+            None => None,
+        };
+
+        let graph_size = triples.len();
+        match triples.finish() {
+            Ok(triples) => Ok((triples, graph_size, dictionary, named_graphs)),
+            Err(err) => Err(ParserError::Graph(err.to_string())),
+        }
+    }
+
+    /// Parses the triples read from any source, such as a pipe, in a single
+    /// pass. Every term is mapped to an integer the first time it is seen;
+    /// thus, the triples are kept in memory in a compact manner until the
+    /// Dictionary, which sorts the terms, can be created.
+    fn parse_reader(
+        reader: Box<dyn BufRead>,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+    ) -> RdfParserResult {
+        let mut graph = InternedGraph::default();
+        let mut blank_nodes = options.blank_nodes();
+
+        Self::parser_fn(reader, options, errors, &mut |triple: Triple,
+                                                       graph_name: Option<
+            GraphName,
+        >| {
+            graph.push(
+                &blank_nodes.relabel(triple.subject.to_string()),
+                &triple.predicate.to_string(),
+                &blank_nodes.relabel(triple.object.to_string()),
+                graph_name
+                    .filter(|_| options.graph_policy.is_keep())
+                    .map(|graph_name| blank_nodes.relabel(graph_name.to_string()))
+                    .as_deref(),
+            );
             Ok(())
-        } as Result<(), E>)
-        {
-            return Err(ParserError::Graph(err));
         }
+            as Result<(), E>)?;
 
-        Ok((graph, dictionary))
+        graph.finish(reference_system, options.dictionary_format)
     }
 
     fn parser_fn(
-        path: &str,
-        on_triple: &mut impl FnMut(Triple<'_>) -> Result<(), E>,
-    ) -> Result<(), String> {
-        // We open a reader for the file that is requested to be read. The idea
-        // is that we will iterate over the triples stored in a certain file
-        let reader = BufReader::new(match File::open(path) {
-            Ok(file) => file,
-            Err(_) => return Err(String::from("Cannot open the file")),
-        });
-
+        reader: Box<dyn BufRead>,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+        on_statement: &mut impl FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+    ) -> Result<(), ParserError> {
         // We create a parser that will be in charge of reading the file retrieving
         // the triples that are stored in the provided file
-        let mut parser = Self::concrete_parser(reader);
+        let mut parser = Self::concrete_parser(reader, options);
+        let mut last_error = None;
 
         while !parser.is_end() {
-            if parser.parse_step(on_triple).is_err() {
-                // We skip the line if it is not a valid triple
-                continue;
+            match parser.parse_step(on_statement) {
+                Ok(_) => last_error = None,
+                Err(err) => {
+                    let error = syntax_error(&err);
+                    // A parser that cannot recover from an error keeps on
+                    // returning it; as such, it cannot be skipped
+                    if last_error.as_ref() == Some(&error) {
+                        return Err(ParserError::Syntax(error));
+                    }
+                    errors.handle(error.clone())?;
+                    last_error = Some(error);
+                }
             }
         }
 
         Ok(())
     }
 
-    fn concrete_parser(reader: BufReader<File>) -> T;
+    fn concrete_parser(reader: Box<dyn BufRead>, options: &ParserOptions) -> T;
 }
 
 pub struct RdfParser;
 
 impl RdfParser {
-    pub fn parse(path: &str, reference_system: &ReferenceSystem) -> RdfParserResult {
-        match path.split('.').last() {
-            Some("nt") => NTriples::parse(path, reference_system),
-            Some("ttl") => Turtle::parse(path, reference_system),
-            Some("rdf") => RdfXml::parse(path, reference_system),
-            Some(format) => Err(ParserError::NotSupportedFormat(format.to_string())),
-            None => Err(ParserError::NoFormatProvided),
+    /// Parses the RDF dump in the provided path; if no format is provided, it
+    /// is guessed from the path or from the content of the dump
+    pub fn parse(
+        path: &str,
+        format: Option<RdfFormat>,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+    ) -> RdfParserResult {
+        let format = match format {
+            Some(format) => format,
+            None => guess_format(path)?,
+        };
+
+        match format {
+            RdfFormat::NTriples => NTriples::parse(path, reference_system, options, errors),
+            RdfFormat::Turtle => Turtle::parse(path, reference_system, options, errors),
+            RdfFormat::RdfXml => RdfXml::parse(path, reference_system, options, errors),
+            RdfFormat::NQuads => NQuads::parse(path, reference_system, options, errors),
+            RdfFormat::TriG => TriG::parse(path, reference_system, options, errors),
+            RdfFormat::JsonLd => JsonLd::parse(path, reference_system, options, errors),
+            RdfFormat::Hdt => Hdt::parse(path, reference_system, options),
+        }
+    }
+
+    pub fn parse_reader(
+        reader: impl BufRead + 'static,
+        format: RdfFormat,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+    ) -> RdfParserResult {
+        let reader = decompress(reader)?;
+        match format {
+            RdfFormat::NTriples => {
+                NTriples::parse_reader(reader, reference_system, options, errors)
+            }
+            RdfFormat::Turtle => Turtle::parse_reader(reader, reference_system, options, errors),
+            RdfFormat::RdfXml => RdfXml::parse_reader(reader, reference_system, options, errors),
+            RdfFormat::NQuads => NQuads::parse_reader(reader, reference_system, options, errors),
+            RdfFormat::TriG => TriG::parse_reader(reader, reference_system, options, errors),
+            RdfFormat::JsonLd => JsonLd::parse_reader(reader, reference_system, options, errors),
+            RdfFormat::Hdt => Hdt::parse_reader(reader, reference_system, options),
+        }
+    }
+
+    pub fn parse_triples(
+        triples: impl IntoIterator<Item = StringTriple>,
+        reference_system: &ReferenceSystem,
+        options: &ParserOptions,
+    ) -> RdfParserResult {
+        let mut graph = InternedGraph::default();
+        let mut blank_nodes = options.blank_nodes();
+        triples
+            .into_iter()
+            .for_each(|(subject, predicate, object)| {
+                graph.push(
+                    &blank_nodes.relabel(subject),
+                    &predicate,
+                    &blank_nodes.relabel(object),
+                    None,
+                )
+            });
+        graph.finish(reference_system, options.dictionary_format)
+    }
+
+    pub(crate) fn parse_streaming(
+        path: &str,
+        format: Option<RdfFormat>,
+        reference_system: &ReferenceSystem,
+        buffer_size: usize,
+        options: &ParserOptions,
+        errors: &mut ErrorHandler,
+    ) -> RdfStreamResult {
+        let format = match format {
+            Some(format) => format,
+            None => guess_format(path)?,
+        };
+
+        match format {
+            RdfFormat::NTriples => {
+                NTriples::parse_streaming(path, reference_system, buffer_size, options, errors)
+            }
+            RdfFormat::Turtle => {
+                Turtle::parse_streaming(path, reference_system, buffer_size, options, errors)
+            }
+            RdfFormat::RdfXml => {
+                RdfXml::parse_streaming(path, reference_system, buffer_size, options, errors)
+            }
+            RdfFormat::NQuads => {
+                NQuads::parse_streaming(path, reference_system, buffer_size, options, errors)
+            }
+            RdfFormat::TriG => {
+                TriG::parse_streaming(path, reference_system, buffer_size, options, errors)
+            }
+            RdfFormat::JsonLd => {
+                JsonLd::parse_streaming(path, reference_system, buffer_size, options, errors)
+            }
+            RdfFormat::Hdt => Hdt::parse_streaming(path, reference_system, buffer_size, options),
         }
     }
 }
+
+/// Handles the syntax errors found while parsing, following the provided
+/// [`ErrorStrategy`]
+pub(crate) struct ErrorHandler {
+    strategy: ErrorStrategy,
+    report: ParseReport,
+}
+
+impl ErrorHandler {
+    pub(crate) fn new(strategy: ErrorStrategy) -> Self {
+        ErrorHandler {
+            strategy,
+            report: ParseReport::default(),
+        }
+    }
+
+    pub(crate) fn into_report(self) -> ParseReport {
+        self.report
+    }
+
+    fn handle(&mut self, error: SyntaxError) -> Result<(), ParserError> {
+        match self.strategy {
+            ErrorStrategy::Strict => Err(ParserError::Syntax(error)),
+            ErrorStrategy::Lenient(max_errors) => {
+                self.report.skipped += 1;
+                if self.report.errors.len() < max_errors {
+                    self.report.errors.push(error);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn syntax_error(err: &impl ParseError) -> SyntaxError {
+    let (line, column) = match err.textual_position() {
+        Some(position) => (position.line_number(), position.byte_number()),
+        None => (0, 0),
+    };
+    SyntaxError {
+        line,
+        column,
+        message: err.to_string(),
+    }
+}
+
+fn open(path: &str) -> Result<Box<dyn BufRead>, ParserError> {
+    // We open a reader for the file that is requested to be read. The idea
+    // is that we will iterate over the triples stored in a certain file
+    match File::open(path) {
+        Ok(file) => decompress(BufReader::new(file)),
+        Err(_) => Err(ParserError::Dictionary(String::from(
+            "Cannot open the file",
+        ))),
+    }
+}
+
+/// Encodes a triple into the indices of its terms, sorted following the
+/// provided reference system; that is, `(first, second, third)`
+fn encode(
+    dictionary: &Dictionary,
+    reference_system: &ReferenceSystem,
+    subject: &str,
+    predicate: &str,
+    object: &str,
+) -> EncodedTriple {
+    order(
+        reference_system,
+        dictionary.get_subject_idx_unchecked(subject) as u32,
+        dictionary.get_predicate_idx_unchecked(predicate) as u32,
+        dictionary.get_object_idx_unchecked(object) as u32,
+    )
+}
+
+/// Sorts the indices of the terms of a triple following the provided
+/// reference system
+fn order(reference_system: &ReferenceSystem, sidx: u32, pidx: u32, oidx: u32) -> EncodedTriple {
+    match reference_system {
+        ReferenceSystem::SPO => (sidx, pidx, oidx),
+        ReferenceSystem::SOP => (sidx, oidx, pidx),
+        ReferenceSystem::PSO => (pidx, sidx, oidx),
+        ReferenceSystem::POS => (pidx, oidx, sidx),
+        ReferenceSystem::OPS => (oidx, pidx, sidx),
+        ReferenceSystem::OSP => (oidx, sidx, pidx),
+    }
+}
+
+fn merge_to_set(terms: ExternalSorter<String>) -> Result<Set, ParserError> {
+    let mut terms = merge(terms)?;
+    let set = Set::new(terms.by_ref());
+    check_merge(&mut terms)?;
+    match set {
+        Ok(set) => Ok(set),
+        Err(err) => Err(ParserError::Dictionary(err.to_string())),
+    }
+}
+
+fn merge(terms: ExternalSorter<String>) -> Result<Merge<String>, ParserError> {
+    match terms.finish() {
+        Ok(terms) => Ok(terms),
+        Err(err) => Err(ParserError::Dictionary(err.to_string())),
+    }
+}
+
+/// A merge stops at the first error while reading the runs, which has to be
+/// checked once it has been consumed
+fn check_merge(terms: &mut Merge<String>) -> Result<(), ParserError> {
+    match terms.error() {
+        Some(err) => Err(ParserError::Dictionary(err.to_string())),
+        None => Ok(()),
+    }
+}
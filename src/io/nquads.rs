@@ -0,0 +1,17 @@
+use rio_turtle::NQuadsParser;
+use rio_turtle::TurtleError;
+use std::io::BufRead;
+
+use super::statements::Quads;
+use super::Backend;
+use super::ParserOptions;
+
+type NQuadsReaderParser = Quads<NQuadsParser<Box<dyn BufRead>>>;
+
+pub struct NQuads;
+
+impl Backend<NQuadsReaderParser, TurtleError> for NQuads {
+    fn concrete_parser(reader: Box<dyn BufRead>, _: &ParserOptions) -> NQuadsReaderParser {
+        Quads(NQuadsParser::new(reader))
+    }
+}
@@ -1,16 +1,17 @@
 use rio_turtle::NTriplesParser;
 use rio_turtle::TurtleError;
-use std::fs::File;
-use std::io::BufReader;
+use std::io::BufRead;
 
+use super::statements::Triples;
 use super::Backend;
+use super::ParserOptions;
 
-type NTriplesFileParser = NTriplesParser<BufReader<File>>;
+type NTriplesReaderParser = Triples<NTriplesParser<Box<dyn BufRead>>>;
 
 pub struct NTriples;
 
-impl Backend<NTriplesFileParser, TurtleError> for NTriples {
-    fn concrete_parser(reader: BufReader<File>) -> NTriplesFileParser {
-        NTriplesParser::new(reader)
+impl Backend<NTriplesReaderParser, TurtleError> for NTriples {
+    fn concrete_parser(reader: Box<dyn BufRead>, _: &ParserOptions) -> NTriplesReaderParser {
+        Triples(NTriplesParser::new(reader))
     }
 }
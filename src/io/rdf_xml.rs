@@ -1,16 +1,17 @@
 use rio_xml::RdfXmlError;
 use rio_xml::RdfXmlParser;
-use std::fs::File;
-use std::io::BufReader;
+use std::io::BufRead;
 
+use super::statements::Triples;
 use super::Backend;
+use super::ParserOptions;
 
-type RdfXmlFileParser = RdfXmlParser<BufReader<File>>;
+type RdfXmlReaderParser = Triples<RdfXmlParser<Box<dyn BufRead>>>;
 
 pub struct RdfXml;
 
-impl Backend<RdfXmlFileParser, RdfXmlError> for RdfXml {
-    fn concrete_parser(reader: BufReader<File>) -> RdfXmlFileParser {
-        RdfXmlParser::new(reader, None)
+impl Backend<RdfXmlReaderParser, RdfXmlError> for RdfXml {
+    fn concrete_parser(reader: Box<dyn BufRead>, _: &ParserOptions) -> RdfXmlReaderParser {
+        Triples(RdfXmlParser::new(reader, None))
     }
 }
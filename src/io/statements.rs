@@ -0,0 +1,65 @@
+use rio_api::model::GraphName;
+use rio_api::model::Triple;
+use rio_api::parser::QuadsParser;
+use rio_api::parser::TriplesParser;
+use std::error::Error;
+
+/// A parser of the statements of an RDF dump; that is, its triples, together
+/// with the name of the graph they belong to, which is `None` for the default
+/// graph. As such, formats with and without named graphs are parsed alike.
+pub(crate) trait StatementsParser {
+    type Error: Error;
+
+    fn parse_step<E: From<Self::Error>>(
+        &mut self,
+        on_statement: &mut impl FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+    ) -> Result<(), E>;
+
+    fn is_end(&self) -> bool;
+}
+
+/// The statements of a format without named graphs, all of which belong to
+/// the default graph
+pub(crate) struct Triples<P>(pub(crate) P);
+
+/// The statements of a format with named graphs
+pub(crate) struct Quads<P>(pub(crate) P);
+
+impl<P: TriplesParser> StatementsParser for Triples<P> {
+    type Error = P::Error;
+
+    fn parse_step<E: From<Self::Error>>(
+        &mut self,
+        on_statement: &mut impl FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.0.parse_step(&mut |triple| on_statement(triple, None))
+    }
+
+    fn is_end(&self) -> bool {
+        self.0.is_end()
+    }
+}
+
+impl<P: QuadsParser> StatementsParser for Quads<P> {
+    type Error = P::Error;
+
+    fn parse_step<E: From<Self::Error>>(
+        &mut self,
+        on_statement: &mut impl FnMut(Triple<'_>, Option<GraphName<'_>>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.0.parse_step(&mut |quad| {
+            on_statement(
+                Triple {
+                    subject: quad.subject,
+                    predicate: quad.predicate,
+                    object: quad.object,
+                },
+                quad.graph_name,
+            )
+        })
+    }
+
+    fn is_end(&self) -> bool {
+        self.0.is_end()
+    }
+}
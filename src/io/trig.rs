@@ -0,0 +1,17 @@
+use rio_turtle::TriGParser;
+use rio_turtle::TurtleError;
+use std::io::BufRead;
+
+use super::statements::Quads;
+use super::Backend;
+use super::ParserOptions;
+
+type TriGReaderParser = Quads<TriGParser<Box<dyn BufRead>>>;
+
+pub struct TriG;
+
+impl Backend<TriGReaderParser, TurtleError> for TriG {
+    fn concrete_parser(reader: Box<dyn BufRead>, _: &ParserOptions) -> TriGReaderParser {
+        Quads(TriGParser::new(reader, None))
+    }
+}
@@ -1,16 +1,17 @@
 use rio_turtle::TurtleError;
 use rio_turtle::TurtleParser;
-use std::fs::File;
-use std::io::BufReader;
+use std::io::BufRead;
 
+use super::statements::Triples;
 use super::Backend;
+use super::ParserOptions;
 
-type TurtleFileParser = TurtleParser<BufReader<File>>;
+type TurtleReaderParser = Triples<TurtleParser<Box<dyn BufRead>>>;
 
 pub struct Turtle;
 
-impl Backend<TurtleFileParser, TurtleError> for Turtle {
-    fn concrete_parser(reader: BufReader<File>) -> TurtleFileParser {
-        TurtleParser::new(reader, None)
+impl Backend<TurtleReaderParser, TurtleError> for Turtle {
+    fn concrete_parser(reader: Box<dyn BufRead>, _: &ParserOptions) -> TurtleReaderParser {
+        Triples(TurtleParser::new(reader, None))
     }
 }
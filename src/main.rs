@@ -2,8 +2,10 @@ use clap::Parser;
 use remote_hdt::storage::layout::tabular::TabularLayout;
 use remote_hdt::storage::params::Backend;
 use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::RdfFormat;
 use remote_hdt::storage::params::ReferenceSystem;
 use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
 use remote_hdt::storage::Storage;
 use remote_hdt::storage::StorageResult;
 
@@ -14,18 +16,32 @@ struct Args {
     #[arg(short, long)]
     rdf: String,
 
+    /// Format of the input RDF file; if it is not provided, it is guessed from
+    /// the extension of the file or from its content
+    #[arg(short, long)]
+    format: Option<RdfFormat>,
+
     /// Output Zarr directory
     #[arg(short, long, default_value = "root.zarr")]
     zarr: String,
+
+    /// Number of threads used for encoding the shards; 0 uses all the CPUs
+    #[arg(short, long, default_value_t = 0)]
+    threads: usize,
 }
 
 fn main() -> StorageResult<()> {
     let args: Args = Args::parse();
-    Storage::new(TabularLayout, Serialization::Sparse).serialize(
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    if let Some(format) = args.format {
+        storage.set_rdf_format(format);
+    }
+    storage.serialize(
         Backend::FileSystem(&args.zarr),
         &args.rdf,
         ChunkingStrategy::Chunk,
         ReferenceSystem::SPO,
+        ThreadingStrategy::Multi(args.threads),
     )?;
     Ok(())
 }
@@ -1,21 +1,23 @@
-use parking_lot::Mutex;
 use sprs::TriMat;
 use std::num::NonZeroU64;
 use std::sync::atomic::Ordering;
 use zarrs::array::codec::array_to_bytes::sharding::ShardingCodecBuilder;
 use zarrs::array::codec::ArrayToBytesCodecTraits;
 use zarrs::array::codec::GzipCodec;
+use zarrs::array::Array;
 use zarrs::array::ChunkGrid;
 use zarrs::array::DataType;
 use zarrs::array::DimensionName;
 use zarrs::array::FillValue;
+use zarrs::storage::ReadableWritableStorageTraits;
 
+use super::serialize_triples;
 use super::ChunkingStrategy;
 use super::Dimensionality;
 use super::ReferenceSystem;
 use super::StorageResult;
 
-use crate::io::Graph;
+use crate::io::external::EncodedTriple;
 use crate::storage::layout::LayoutOps;
 use crate::storage::AtomicZarrType;
 use crate::storage::Layout;
@@ -100,8 +102,31 @@ impl Layout<Chunk> for MatrixLayout {
 }
 
 impl LayoutOps<Chunk> for MatrixLayout {
-    fn graph_iter(&self, graph: Graph) -> Vec<Chunk> {
-        graph
+    fn serialize_triples(
+        &mut self,
+        arr: &Array<dyn ReadableWritableStorageTraits>,
+        triples: &mut dyn Iterator<Item = EncodedTriple>,
+    ) -> StorageResult<()> {
+        serialize_triples(self, arr, triples)
+    }
+
+    fn chunk_iter<'a>(
+        &self,
+        triples: &'a mut dyn Iterator<Item = EncodedTriple>,
+        rows: usize,
+    ) -> Box<dyn Iterator<Item = Chunk> + 'a> {
+        // Every row of the matrix is a chunk, even if there are no triples
+        // whose first term is the one corresponding to that row
+        let mut triples = triples.peekable();
+        Box::new((0..rows as u32).map(move |first_term| {
+            let mut chunk = Chunk::new();
+            while let Some((_, second_term, third_term)) =
+                triples.next_if(|&(first, _, _)| first == first_term)
+            {
+                chunk.push((second_term, third_term));
+            }
+            chunk
+        }))
     }
 
     fn store_chunk_elements(&self, chunk: &[Chunk], columns: usize) -> Vec<u32> {
@@ -128,8 +153,8 @@ impl LayoutOps<Chunk> for MatrixLayout {
     }
 
     fn retrieve_chunk_elements(
-        &mut self,
-        matrix: &Mutex<TriMat<usize>>,
+        &self,
+        matrix: &mut TriMat<usize>,
         first_term_index: usize,
         chunk: &[u32],
     ) {
@@ -138,11 +163,7 @@ impl LayoutOps<Chunk> for MatrixLayout {
             .enumerate()
             .for_each(|(third_term_idx, &second_term_idx)| {
                 if second_term_idx != 0 {
-                    matrix.lock().add_triplet(
-                        first_term_index,
-                        third_term_idx,
-                        second_term_idx as usize,
-                    );
+                    matrix.add_triplet(first_term_index, third_term_idx, second_term_idx as usize);
                 }
             })
     }
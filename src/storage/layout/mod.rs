@@ -1,7 +1,11 @@
-use parking_lot::Mutex;
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use rayon::iter::IndexedParallelIterator;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use serde_json::Map;
+use serde_json::Value;
 use sprs::TriMat;
-use std::sync::atomic::AtomicU64;
-use std::sync::atomic::Ordering;
 use zarrs::array::codec::ArrayToBytesCodecTraits;
 use zarrs::array::Array;
 use zarrs::array::ChunkGrid;
@@ -9,15 +13,15 @@ use zarrs::array::DataType;
 use zarrs::array::DimensionName;
 use zarrs::array::FillValue;
 use zarrs::array_subset::ArraySubset;
-use zarrs::storage::store::FilesystemStore;
+use zarrs::storage::AsyncReadableStorageTraits;
 use zarrs::storage::ReadableStorageTraits;
+use zarrs::storage::ReadableWritableStorageTraits;
 
-use crate::dictionary::Dictionary;
 use crate::error::RemoteHDTError;
+use crate::io::external::EncodedTriple;
 use crate::io::Graph;
 use crate::utils::columns_per_shard;
 use crate::utils::rows_per_shard;
-use crate::utils::value_to_term;
 
 use super::ChunkingStrategy;
 use super::Dimensionality;
@@ -30,26 +34,13 @@ type ArrayToBytesCodec = Box<dyn ArrayToBytesCodecTraits>;
 pub mod matrix;
 pub mod tabular;
 
-pub trait LayoutOps<C> {
+#[async_trait]
+pub trait LayoutOps<C>: Send + Sync {
     fn retrieve_attributes(
         &mut self,
-        arr: &Array<dyn ReadableStorageTraits>,
-    ) -> StorageResult<Dictionary> {
+        attributes: &Map<String, Value>,
+    ) -> StorageResult<ReferenceSystem> {
         // 4. We get the attributes so we can obtain some values that we will need
-        let attributes = arr.attributes();
-
-        let subjects = &value_to_term(match attributes.get("subjects") {
-            Some(subjects) => subjects,
-            None => return Err(RemoteHDTError::SubjectsNotInJSON),
-        });
-        let predicates = &value_to_term(match attributes.get("predicates") {
-            Some(predicates) => predicates,
-            None => return Err(RemoteHDTError::PredicatesNotInJSON),
-        });
-        let objects = &value_to_term(match attributes.get("objects") {
-            Some(objects) => objects,
-            None => return Err(RemoteHDTError::ObjectsNotInJSON),
-        });
 
         let reference_system: ReferenceSystem = match attributes.get("reference_system") {
             Some(reference_system) => reference_system,
@@ -59,64 +50,52 @@ pub trait LayoutOps<C> {
         .unwrap()
         .into();
 
-        Ok(Dictionary::from_vec_str(
-            reference_system,
-            subjects,
-            predicates,
-            objects,
-        ))
+        Ok(reference_system)
     }
 
-    fn serialize(&mut self, arr: &Array<FilesystemStore>, graph: Graph) -> StorageResult<()> {
-        let columns = arr.shape()[1] as usize;
-        let count = AtomicU64::new(0);
-        let binding = self.graph_iter(graph.to_owned());
-        let iter = binding.chunks_exact(rows_per_shard(arr) as usize);
-        let remainder = iter.remainder();
-
-        for chunk in iter {
-            let slice = self.store_chunk_elements(chunk, columns);
-            arr.store_chunk_elements::<u32>(&[count.load(Ordering::Relaxed), 0], slice)?;
-            count.fetch_add(1, Ordering::Relaxed);
-        }
-
-        if !remainder.is_empty() {
-            // first we count the number of shards that have been processed, and
-            // multiply it by the number of chunks in every shard. Hence, we will
-            // obtain the number of rows that have been processed
-            let rows_processed = count.load(Ordering::Relaxed) * rows_per_shard(arr);
-            // then we obtain the size of the last shard that is going to be
-            // processed; it is equals to the size of the remainder
-            let last_shard_size = remainder.len() as u64;
-            // lastly, we store the elements in the provided subset
-            arr.store_array_subset_elements::<u32>(
-                &ArraySubset::new_with_start_shape(
-                    vec![rows_processed, 0],
-                    vec![last_shard_size, columns_per_shard(arr)],
-                )?,
-                self.store_chunk_elements(remainder, columns),
-            )?;
-        }
-
-        Ok(())
+    fn serialize(
+        &mut self,
+        arr: &Array<dyn ReadableWritableStorageTraits>,
+        graph: Graph,
+    ) -> StorageResult<()> {
+        let mut triples = graph
+            .into_iter()
+            .enumerate()
+            .flat_map(|(first_term, triples)| {
+                triples.into_iter().map(move |(second_term, third_term)| {
+                    (first_term as u32, second_term, third_term)
+                })
+            });
+        self.serialize_triples(arr, &mut triples)
     }
 
+    /// Stores the triples shard by shard, so that only a few of them are kept
+    /// in memory at a time; that is, one for each thread in the current pool.
+    /// The triples are expected to be sorted by their first term, as they are
+    /// not buffered before being written into the array. Layouts implement it
+    /// by calling [`serialize_triples`], as their chunks must be [`Send`].
+    fn serialize_triples(
+        &mut self,
+        arr: &Array<dyn ReadableWritableStorageTraits>,
+        triples: &mut dyn Iterator<Item = EncodedTriple>,
+    ) -> StorageResult<()>;
+
     fn parse(
         &mut self,
         arr: &Array<dyn ReadableStorageTraits>,
         dimensionality: &Dimensionality,
     ) -> StorageResult<ZarrArray> {
-        // First, we create the 2D matrix in such a manner that the number of
-        // rows is the same as the size of the first terms; i.e, in the SPO
-        // orientation, that will be equals to the number of subjects, while
+        // First, we define the shape of the 2D matrix in such a manner that the
+        // number of rows is the same as the size of the first terms; i.e, in the
+        // SPO orientation, that will be equals to the number of subjects, while
         // the number of columns is equals to the size of the third terms; i.e,
         // following the same example as before, it will be equals to the number
         // of objects. In our case the dimensionality abstracts the process
         // of getting the size of the concrete dimension
-        let matrix = Mutex::new(TriMat::new((
+        let shape = (
             dimensionality.first_term_size, // we obtain the size of the first terms
             dimensionality.third_term_size, // we obtain the size of the third terms
-        )));
+        );
 
         // We compute the number of shards; for us to achieve so, we have to obtain
         // first dimension of the chunk grid
@@ -128,45 +107,141 @@ pub trait LayoutOps<C> {
 
         let number_of_columns = arr.shape()[1] as usize;
 
-        // For each chunk in the Zarr array we retrieve it and parse it into a
-        // matrix, inserting the triplet in its corresponding position. The idea
-        // of parsing the array chunk-by-chunk allows us to keep the RAM usage
-        // low, as instead of parsing the whole array, we process smaller pieces
-        // of it. Once we have all the pieces processed, we will have parsed the
-        // whole array
-        for shard in 0..number_of_shards {
-            arr.retrieve_chunk_elements::<u32>(&[shard, 0])?
-                // We divide each shard by the number of columns, as a shard is
-                // composed of chunks having the size of [1, number of cols]
-                .chunks(number_of_columns)
-                .enumerate()
-                .for_each(|(first_term_idx, chunk)| {
-                    self.retrieve_chunk_elements(
-                        &matrix,
-                        first_term_idx + (shard * rows_per_shard(arr)) as usize,
-                        chunk,
-                    );
-                })
-        }
+        // For each shard in the Zarr array we retrieve it and parse it into a
+        // partial matrix, inserting the triplets in their corresponding position.
+        // Shards are fetched and decoded concurrently, as many at a time as
+        // threads in the current pool; hence, when the array is stored remotely,
+        // the latency of the requests is overlapped. As every shard has its own
+        // matrix, no synchronization is needed among the threads
+        let matrices = (0..number_of_shards)
+            .into_par_iter()
+            .map(|shard| {
+                let mut matrix = TriMat::new(shape);
+                self.parse_shard(
+                    &mut matrix,
+                    (shard * rows_per_shard(arr)) as usize,
+                    &arr.retrieve_chunk_elements::<u32>(&[shard, 0])?,
+                    number_of_columns,
+                );
+                Ok(matrix)
+            })
+            .collect::<StorageResult<Vec<_>>>()?;
+
+        Ok(merge_matrices(shape, matrices))
+    }
+
+    /// The asynchronous counterpart of [`LayoutOps::parse`]. Instead of being
+    /// retrieved by a pool of threads, all the shards are requested at once,
+    /// and they are decoded as soon as they arrive.
+    async fn async_parse(
+        &self,
+        arr: &Array<dyn AsyncReadableStorageTraits>,
+        dimensionality: &Dimensionality,
+    ) -> StorageResult<ZarrArray> {
+        let shape = (
+            dimensionality.first_term_size,
+            dimensionality.third_term_size,
+        );
+
+        let number_of_shards = match arr.chunk_grid_shape() {
+            Some(chunk_grid) => chunk_grid[0],
+            None => 0,
+        };
+
+        let number_of_columns = arr.shape()[1] as usize;
+
+        let matrices = try_join_all((0..number_of_shards).map(|shard| async move {
+            let mut matrix = TriMat::new(shape);
+            self.parse_shard(
+                &mut matrix,
+                (shard * rows_per_shard(arr)) as usize,
+                &arr.async_retrieve_chunk_elements::<u32>(&[shard, 0])
+                    .await?,
+                number_of_columns,
+            );
+            Ok::<_, RemoteHDTError>(matrix)
+        }))
+        .await?;
 
-        // We use a CSC Matrix because typically, RDF knowledge graphs tend to
-        // have more rows than columns; as such, CSC matrices are optimized
-        // for that precise scenario
-        let x = matrix.lock();
-        Ok(x.to_csc())
+        Ok(merge_matrices(shape, matrices))
     }
 
-    fn graph_iter(&self, graph: Graph) -> Vec<C>;
+    /// Decodes a single shard of the array into its triples, given by their
+    /// first, second and third terms. The padding of the last shard, whose
+    /// second term is 0, is skipped, as it does not belong to any triple.
+    fn retrieve_shard(
+        &self,
+        arr: &Array<dyn ReadableStorageTraits>,
+        shard: u64,
+        dimensionality: &Dimensionality,
+    ) -> StorageResult<Vec<(usize, usize, usize)>> {
+        let mut matrix = TriMat::new((
+            dimensionality.first_term_size,
+            dimensionality.third_term_size,
+        ));
+        self.parse_shard(
+            &mut matrix,
+            (shard * rows_per_shard(arr)) as usize,
+            &arr.retrieve_chunk_elements::<u32>(&[shard, 0])?,
+            arr.shape()[1] as usize,
+        );
+
+        Ok(matrix
+            .triplet_iter()
+            .filter(|(&second_term, _)| second_term != 0)
+            .map(|(&second_term, (first_term, third_term))| (first_term, second_term, third_term))
+            .collect())
+    }
+
+    fn parse_shard(
+        &self,
+        matrix: &mut TriMat<usize>,
+        first_term_offset: usize,
+        shard: &[u32],
+        number_of_columns: usize,
+    ) {
+        shard
+            // We divide each shard by the number of columns, as a shard is
+            // composed of chunks having the size of [1, number of cols]
+            .chunks(number_of_columns)
+            .enumerate()
+            .for_each(|(first_term_idx, chunk)| {
+                self.retrieve_chunk_elements(matrix, first_term_idx + first_term_offset, chunk);
+            });
+    }
+
+    fn chunk_iter<'a>(
+        &self,
+        triples: &'a mut dyn Iterator<Item = EncodedTriple>,
+        rows: usize,
+    ) -> Box<dyn Iterator<Item = C> + 'a>;
     fn store_chunk_elements(&self, chunk: &[C], columns: usize) -> Vec<u32>;
     fn retrieve_chunk_elements(
-        &mut self,
-        matrix: &Mutex<TriMat<usize>>,
+        &self,
+        matrix: &mut TriMat<usize>,
         first_term_idx: usize,
         chunk: &[u32],
     );
     fn sharding_factor(&self, dimensionality: &Dimensionality) -> usize;
 }
 
+fn merge_matrices(shape: (usize, usize), matrices: Vec<TriMat<usize>>) -> ZarrArray {
+    // Once we have all the pieces processed, we merge them in order, so that
+    // we will have parsed the whole array
+    let nnz = matrices.iter().map(|matrix| matrix.nnz()).sum();
+    let mut matrix = TriMat::with_capacity(shape, nnz);
+    matrices.iter().for_each(|partial| {
+        partial
+            .triplet_iter()
+            .for_each(|(&value, (row, col))| matrix.add_triplet(row, col, value))
+    });
+
+    // We use a CSC Matrix because typically, RDF knowledge graphs tend to
+    // have more rows than columns; as such, CSC matrices are optimized
+    // for that precise scenario
+    matrix.to_csc()
+}
+
 pub trait Layout<C>: LayoutOps<C> {
     fn name(&self) -> String;
     fn shape(&self, dimensionality: &Dimensionality) -> Vec<u64>;
@@ -183,3 +258,66 @@ pub trait Layout<C>: LayoutOps<C> {
         dimensionality: &Dimensionality,
     ) -> StorageResult<ArrayToBytesCodec>;
 }
+
+/// Stores the triples shard by shard; see [`LayoutOps::serialize_triples`]
+pub(super) fn serialize_triples<C: Send>(
+    layout: &(impl LayoutOps<C> + ?Sized),
+    arr: &Array<dyn ReadableWritableStorageTraits>,
+    triples: &mut dyn Iterator<Item = EncodedTriple>,
+) -> StorageResult<()> {
+    let columns = arr.shape()[1] as usize;
+    let rows_per_shard = rows_per_shard(arr);
+    let mut chunks = layout.chunk_iter(triples, arr.shape()[0] as usize);
+    let mut count = 0;
+
+    loop {
+        // The chunks are grouped into shards sequentially, as they are read
+        // from the iterator, while the shards are built, encoded and stored
+        // concurrently. As every shard is written into its own position,
+        // the result does not depend on the order
+        let shards = (0..rayon::current_num_threads())
+            .map(|_| {
+                chunks
+                    .by_ref()
+                    .take(rows_per_shard as usize)
+                    .collect::<Vec<_>>()
+            })
+            .take_while(|chunk| !chunk.is_empty())
+            .collect::<Vec<_>>();
+
+        if shards.is_empty() {
+            break;
+        }
+
+        shards
+            .into_par_iter()
+            .enumerate()
+            .try_for_each(|(i, chunk)| {
+                let shard = count + i as u64;
+                let rows = chunk.len() as u64;
+                let slice = layout.store_chunk_elements(&chunk, columns);
+                if rows == rows_per_shard {
+                    arr.store_chunk_elements::<u32>(&[shard, 0], slice)?;
+                } else {
+                    // first we count the number of shards that have been processed, and
+                    // multiply it by the number of chunks in every shard. Hence, we will
+                    // obtain the number of rows that have been processed
+                    let rows_processed = shard * rows_per_shard;
+                    // lastly, we store the elements in the provided subset, whose size
+                    // is equals to the number of rows in the remainder
+                    arr.store_array_subset_elements::<u32>(
+                        &ArraySubset::new_with_start_shape(
+                            vec![rows_processed, 0],
+                            vec![rows, columns_per_shard(arr)],
+                        )?,
+                        slice,
+                    )?;
+                }
+                Ok::<_, RemoteHDTError>(())
+            })?;
+
+        count += rayon::current_num_threads() as u64;
+    }
+
+    Ok(())
+}
@@ -1,21 +1,23 @@
 use std::num::NonZeroU64;
 
-use parking_lot::Mutex;
 use sprs::TriMat;
 use zarrs::array::codec::array_to_bytes::sharding::ShardingCodecBuilder;
 use zarrs::array::codec::ArrayToBytesCodecTraits;
 use zarrs::array::codec::GzipCodec;
+use zarrs::array::Array;
 use zarrs::array::ChunkGrid;
 use zarrs::array::DataType;
 use zarrs::array::DimensionName;
 use zarrs::array::FillValue;
+use zarrs::storage::ReadableWritableStorageTraits;
 
+use super::serialize_triples;
 use super::ChunkingStrategy;
 use super::Dimensionality;
 use super::ReferenceSystem;
 use super::StorageResult;
 
-use crate::io::Graph;
+use crate::io::external::EncodedTriple;
 use crate::storage::layout::LayoutOps;
 use crate::storage::Layout;
 
@@ -62,17 +64,20 @@ impl Layout<Chunk> for TabularLayout {
 }
 
 impl LayoutOps<Chunk> for TabularLayout {
-    fn graph_iter(&self, graph: Graph) -> Vec<Chunk> {
-        graph
-            .iter()
-            .enumerate()
-            .flat_map(|(first_term, triples)| {
-                triples
-                    .iter()
-                    .map(|&(second_term, third_term)| (first_term as u32, second_term, third_term))
-                    .collect::<Vec<Chunk>>()
-            })
-            .collect::<Vec<Chunk>>()
+    fn serialize_triples(
+        &mut self,
+        arr: &Array<dyn ReadableWritableStorageTraits>,
+        triples: &mut dyn Iterator<Item = EncodedTriple>,
+    ) -> StorageResult<()> {
+        serialize_triples(self, arr, triples)
+    }
+
+    fn chunk_iter<'a>(
+        &self,
+        triples: &'a mut dyn Iterator<Item = EncodedTriple>,
+        _: usize,
+    ) -> Box<dyn Iterator<Item = Chunk> + 'a> {
+        Box::new(triples)
     }
 
     fn store_chunk_elements(&self, chunk: &[Chunk], _: usize) -> Vec<u32> {
@@ -86,14 +91,15 @@ impl LayoutOps<Chunk> for TabularLayout {
     }
 
     fn retrieve_chunk_elements(
-        &mut self,
-        matrix: &Mutex<TriMat<usize>>,
+        &self,
+        matrix: &mut TriMat<usize>,
         _first_term_index: usize, // TODO: will first_term_index instead of chunk[0] do the trick?
         chunk: &[u32],
     ) {
-        matrix
-            .lock()
-            .add_triplet(chunk[0] as usize, chunk[2] as usize, chunk[1] as usize);
+        // The padding of the last shard, whose second term is 0, is skipped
+        if chunk[1] != 0 {
+            matrix.add_triplet(chunk[0] as usize, chunk[2] as usize, chunk[1] as usize);
+        }
     }
 
     fn sharding_factor(&self, dimensionality: &Dimensionality) -> usize {
@@ -1,29 +1,73 @@
+use fcsd::Set;
+use futures::future::try_join_all;
+use futures::try_join;
+use object_store::http::HttpBuilder;
+use object_store::local::LocalFileSystem;
+use rayon::ThreadPool;
 use serde_json::Map;
+use serde_json::Value;
 use sprs::CsMat;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::Write;
+use std::mem;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
 use zarrs::array::Array;
 use zarrs::array::ArrayBuilder;
-use zarrs::array_subset::ArraySubset;
+use zarrs::group::Group;
 use zarrs::group::GroupBuilder;
+use zarrs::storage::store::AsyncObjectStore;
 use zarrs::storage::store::FilesystemStore;
 use zarrs::storage::store::HTTPStore;
+use zarrs::storage::AsyncReadableStorageTraits;
 use zarrs::storage::ReadableStorageTraits;
+use zarrs::storage::ReadableWritableStorageTraits;
+use zarrs::storage::StoreKey;
 
+use crate::dictionary::lazy::LazySet;
+use crate::dictionary::partition::Partition;
+use crate::dictionary::partition::TermKind;
 use crate::dictionary::Dictionary;
+use crate::dictionary::Terms;
+use crate::error::OpsError;
+use crate::error::ParseReport;
 use crate::error::RemoteHDTError;
+use crate::io::blank_nodes::scope;
+use crate::io::external::EncodedTriple;
+use crate::io::external::Merge;
+use crate::io::formatter::RdfFormatter;
+use crate::io::hdt::Hdt;
+use crate::io::ErrorHandler;
 use crate::io::Graph;
+use crate::io::NamedGraphStream;
+use crate::io::NamedGraphs;
+use crate::io::ParserOptions;
 use crate::io::RdfParser;
-use crate::utils::rdf_to_value;
+use crate::io::RdfParserResult;
+use crate::utils::async_zarr_to_integers;
+use crate::utils::async_zarr_to_set;
+use crate::utils::integers_to_zarr;
+use crate::utils::set_to_zarr;
+use crate::utils::zarr_to_integers;
+use crate::utils::zarr_to_set;
 
 use self::layout::Layout;
 use self::params::Backend;
+use self::params::BlankNodePolicy;
 use self::params::ChunkingStrategy;
+use self::params::DictionaryFormat;
+use self::params::DictionaryStrategy;
 use self::params::Dimensionality;
+use self::params::ErrorStrategy;
+use self::params::GraphPolicy;
+use self::params::MemoryStrategy;
+use self::params::RdfFormat;
 use self::params::ReferenceSystem;
 use self::params::Serialization;
+use self::params::ThreadingStrategy;
 
 pub mod layout;
 pub mod ops;
@@ -33,7 +77,26 @@ pub type ZarrArray = CsMat<usize>;
 type AtomicZarrType = AtomicU32;
 pub type StorageResult<T> = Result<T, RemoteHDTError>;
 
+/// The triples resulting from parsing the RDF dump, depending on the
+/// [`MemoryStrategy`] that has been chosen
+enum ParsedGraph {
+    InMemory(Graph, NamedGraphs),
+    Streaming(Merge<EncodedTriple>, Option<Box<NamedGraphStream>>),
+}
+
+const DEFAULT_REPORTED_ERRORS: usize = 10;
+const GROUP_METADATA: &str = "group/zarr.json";
 const ARRAY_NAME: &str = "/group/RemoteHDT"; // TODO: parameterize this
+const SHARED_NAME: &str = "/group/shared";
+const SUBJECTS_NAME: &str = "/group/subjects";
+const PREDICATES_NAME: &str = "/group/predicates";
+const OBJECTS_NAME: &str = "/group/objects";
+const OBJECTS_ATTRIBUTE: &str = "objects";
+const BLANK_NODES_ATTRIBUTE: &str = "blank_nodes";
+const SKOLEM_BASE_ATTRIBUTE: &str = "skolem_base";
+const GRAPHS_METADATA: &str = "group/graphs/zarr.json";
+const GRAPHS_NAME: &str = "/group/graphs";
+const PARTITIONS_NAME: &str = "/group/partitions";
 
 pub struct Storage<C> {
     dictionary: Dictionary,
@@ -42,7 +105,22 @@ pub struct Storage<C> {
     serialization: Serialization,
     reference_system: ReferenceSystem,
     array: Option<Array<dyn ReadableStorageTraits>>,
+    async_array: Option<Array<dyn AsyncReadableStorageTraits>>,
     sparse_array: Option<ZarrArray>,
+    partitions: Vec<Array<dyn ReadableStorageTraits>>,
+    async_partitions: Vec<Array<dyn AsyncReadableStorageTraits>>,
+    sparse_partitions: Vec<ZarrArray>,
+    graph: Option<usize>,
+    dictionary_strategy: Option<DictionaryStrategy>,
+    dictionary_format: DictionaryFormat,
+    memory_strategy: MemoryStrategy,
+    error_strategy: ErrorStrategy,
+    parse_report: ParseReport,
+    graph_policy: GraphPolicy,
+    blank_node_policy: BlankNodePolicy,
+    blank_node_scope: Option<String>,
+    rdf_format: Option<RdfFormat>,
+    jsonld_contexts: HashMap<String, PathBuf>,
 }
 
 impl<C> Storage<C> {
@@ -54,16 +132,151 @@ impl<C> Storage<C> {
             serialization,
             reference_system: ReferenceSystem::SPO,
             array: None,
+            async_array: None,
             sparse_array: None,
+            partitions: Vec::new(),
+            async_partitions: Vec::new(),
+            sparse_partitions: Vec::new(),
+            graph: None,
+            dictionary_strategy: None,
+            dictionary_format: DictionaryFormat::ThreeSection,
+            memory_strategy: MemoryStrategy::InMemory,
+            error_strategy: ErrorStrategy::Lenient(DEFAULT_REPORTED_ERRORS),
+            parse_report: ParseReport::default(),
+            graph_policy: GraphPolicy::Merge,
+            blank_node_policy: BlankNodePolicy::Keep,
+            blank_node_scope: None,
+            rdf_format: None,
+            jsonld_contexts: HashMap::new(),
         }
     }
 
+    /// Sets how the RDF dump is processed by [`Storage::serialize`]; by default,
+    /// it is fully loaded in memory.
+    pub fn set_memory_strategy(&mut self, memory_strategy: MemoryStrategy) -> &mut Self {
+        self.memory_strategy = memory_strategy;
+        self
+    }
+
+    /// Sets how the syntax errors in the RDF dump are handled when serializing
+    /// it; by default, invalid statements are skipped, and the first ones of
+    /// them are reported by [`Storage::get_parse_report`].
+    pub fn set_error_strategy(&mut self, error_strategy: ErrorStrategy) -> &mut Self {
+        self.error_strategy = error_strategy;
+        self
+    }
+
+    /// Sets how the named graphs of N-Quads and TriG dumps are serialized; by
+    /// default, they are merged into the default graph.
+    pub fn set_graph_policy(&mut self, graph_policy: GraphPolicy) -> &mut Self {
+        self.graph_policy = graph_policy;
+        self
+    }
+
+    /// Sets how the blank nodes of the RDF dumps are labeled when serializing
+    /// them; by default, they are stored as they are labeled by the parser.
+    /// The policy of a dataset is recorded in its store, and it is read back
+    /// when the dataset is loaded.
+    pub fn set_blank_node_policy(&mut self, blank_node_policy: BlankNodePolicy) -> &mut Self {
+        self.blank_node_policy = blank_node_policy;
+        self
+    }
+
+    /// Sets the scope by which the blank nodes are relabeled when they are
+    /// scoped or skolemized, instead of the one derived from the path of the
+    /// dump. It is required when serializing a reader or a set of triples, as
+    /// they cannot be told apart otherwise.
+    pub fn set_blank_node_scope(&mut self, scope: &str) -> &mut Self {
+        self.blank_node_scope = Some(scope.to_string());
+        self
+    }
+
+    /// Sets the format of the RDF dumps that are serialized from a path; if it
+    /// is not set, it is guessed from their extension or from their content.
+    pub fn set_rdf_format(&mut self, rdf_format: RdfFormat) -> &mut Self {
+        self.rdf_format = Some(rdf_format);
+        self
+    }
+
+    /// Maps the IRI of a JSON-LD context to a local copy of it, which is read
+    /// instead of fetching the context over the network; this can be done for
+    /// as many contexts as needed.
+    pub fn set_jsonld_context(&mut self, iri: &str, path: impl Into<PathBuf>) -> &mut Self {
+        self.jsonld_contexts.insert(iri.to_string(), path.into());
+        self
+    }
+
+    /// Overrides how the Dictionary is retrieved by [`Storage::load`]; if it is
+    /// not set, it is decided depending on the [`Backend`] that is used.
+    pub fn set_dictionary_strategy(
+        &mut self,
+        dictionary_strategy: DictionaryStrategy,
+    ) -> &mut Self {
+        self.dictionary_strategy = Some(dictionary_strategy);
+        self
+    }
+
+    /// Sets how the terms are split into the sections of the Dictionary by
+    /// [`Storage::serialize`]; by default, subjects and objects are kept apart.
+    /// The format of a dataset is detected when it is loaded.
+    pub fn set_dictionary_format(&mut self, dictionary_format: DictionaryFormat) -> &mut Self {
+        self.dictionary_format = dictionary_format;
+        self
+    }
+
     pub fn get_dictionary(&self) -> Dictionary {
         self.dictionary.to_owned()
     }
 
+    pub fn get_blank_node_policy(&self) -> BlankNodePolicy {
+        self.blank_node_policy.to_owned()
+    }
+
+    /// The statements that were skipped during the last serialization
+    pub fn get_parse_report(&self) -> ParseReport {
+        self.parse_report.to_owned()
+    }
+
+    /// The sparse array of the graph that is currently selected
     pub fn get_sparse_array(&self) -> Option<ZarrArray> {
-        self.sparse_array.to_owned()
+        self.selected_sparse_array().cloned()
+    }
+
+    /// Restricts the queries that are run using [`ops::Ops`] and
+    /// [`ops::AsyncOps`] to the triples of a named graph; if `None` is
+    /// provided, the triples of all the graphs are queried, which is the
+    /// default. The named graphs are only available if the dataset was
+    /// serialized using [`GraphPolicy::Keep`].
+    pub fn select_graph(&mut self, graph: Option<&str>) -> StorageResult<&mut Self> {
+        self.graph = match graph {
+            Some(graph) => match self.dictionary.get_graph_idx(graph) {
+                Some(index) => Some(index),
+                None => return Err(OpsError::GraphNotFound.into()),
+            },
+            None => None,
+        };
+        Ok(self)
+    }
+
+    fn selected_array(&self) -> Option<&Array<dyn ReadableStorageTraits>> {
+        match self.graph {
+            Some(graph) => self.partitions.get(graph),
+            None => self.array.as_ref(),
+        }
+    }
+
+    fn selected_async_array(&self) -> Option<&Array<dyn AsyncReadableStorageTraits>> {
+        match self.graph {
+            Some(graph) => self.async_partitions.get(graph),
+            None => self.async_array.as_ref(),
+        }
+    }
+
+    fn selected_sparse_array(&self) -> Option<&ZarrArray> {
+        match self.graph {
+            Some(graph) => self.sparse_partitions.get(graph),
+            None => self.sparse_array.as_ref(),
+        }
     }
 
     /// # Errors
@@ -76,106 +289,677 @@ impl<C> Storage<C> {
         rdf_path: &'a str,
         chunking_strategy: ChunkingStrategy,
         reference_system: ReferenceSystem,
-        // threading_strategy: ThreadingStrategy, TODO: implement this
+        threading_strategy: ThreadingStrategy,
     ) -> StorageResult<&mut Self> {
-        let path = match store {
-            Backend::FileSystem(path) => {
-                let path = PathBuf::from_str(path)?;
+        let store = writable_store(store)?;
 
-                match path.exists() {
-                    true => return Err(RemoteHDTError::PathExists),
-                    false => path,
-                }
+        // 3. Import the RDF dump using `rdf-rs`. When streaming, the triples are
+        // not collected into a Graph, but sorted on disk by their first term
+        let mut errors = ErrorHandler::new(self.error_strategy);
+        let graph = match self.memory_strategy {
+            MemoryStrategy::InMemory => self.in_memory(RdfParser::parse(
+                rdf_path,
+                self.rdf_format,
+                &reference_system,
+                &self.parser_options(Some(rdf_path))?,
+                &mut errors,
+            ))?,
+            MemoryStrategy::Streaming(buffer_size) => {
+                let (triples, graph_size, dictionary, named_graphs) = RdfParser::parse_streaming(
+                    rdf_path,
+                    self.rdf_format,
+                    &reference_system,
+                    buffer_size,
+                    &self.parser_options(Some(rdf_path))?,
+                    &mut errors,
+                )?;
+                self.dictionary = dictionary;
+                self.dimensionality =
+                    Dimensionality::with_graph_size(&self.dictionary, Some(graph_size));
+                ParsedGraph::Streaming(triples, named_graphs.map(Box::new))
             }
-            Backend::HTTP(_) => return Err(RemoteHDTError::ReadOnlyBackend),
         };
+        self.parse_report = errors.into_report();
 
-        // 2. We can create the FileSystemStore appropiately
-        let store = Arc::new(FilesystemStore::new(path)?);
+        self.write(
+            store,
+            graph,
+            chunking_strategy,
+            reference_system,
+            threading_strategy,
+        )
+    }
 
-        // Create a group and write metadata to filesystem
-        let group = GroupBuilder::new().build(store.clone(), "/group")?;
+    /// Serializes the RDF dump read from any source, such as a pipe, written
+    /// in the provided format. As the source can only be read once, the
+    /// triples are kept in memory, regardless of the [`MemoryStrategy`].
+    pub fn serialize_reader(
+        &mut self,
+        store: Backend<'_>,
+        reader: impl BufRead + 'static,
+        format: RdfFormat,
+        chunking_strategy: ChunkingStrategy,
+        reference_system: ReferenceSystem,
+        threading_strategy: ThreadingStrategy,
+    ) -> StorageResult<&mut Self> {
+        let store = writable_store(store)?;
+        let mut errors = ErrorHandler::new(self.error_strategy);
+        let graph = self.in_memory(RdfParser::parse_reader(
+            reader,
+            format,
+            &reference_system,
+            &self.parser_options(None)?,
+            &mut errors,
+        ))?;
+        self.parse_report = errors.into_report();
+
+        self.write(
+            store,
+            graph,
+            chunking_strategy,
+            reference_system,
+            threading_strategy,
+        )
+    }
+
+    /// Serializes triples that are generated programmatically. Their terms are
+    /// written as in N-Triples; that is, `<http://example.org/alan>` for IRIs,
+    /// and `"Alan Turing"` for literals. The triples are kept in memory,
+    /// regardless of the [`MemoryStrategy`].
+    pub fn serialize_triples(
+        &mut self,
+        store: Backend<'_>,
+        triples: impl IntoIterator<Item = (String, String, String)>,
+        chunking_strategy: ChunkingStrategy,
+        reference_system: ReferenceSystem,
+        threading_strategy: ThreadingStrategy,
+    ) -> StorageResult<&mut Self> {
+        let store = writable_store(store)?;
+        let graph = self.in_memory(RdfParser::parse_triples(
+            triples,
+            &reference_system,
+            &self.parser_options(None)?,
+        ))?;
+        self.parse_report = ParseReport::default();
+
+        self.write(
+            store,
+            graph,
+            chunking_strategy,
+            reference_system,
+            threading_strategy,
+        )
+    }
+
+    fn parser_options<'a>(&'a self, path: Option<&'a str>) -> StorageResult<ParserOptions<'a>> {
+        let scope = match (
+            &self.blank_node_policy,
+            scope(self.blank_node_scope.as_deref(), path),
+        ) {
+            (BlankNodePolicy::Keep, _) => String::new(),
+            (_, Some(scope)) => scope,
+            (_, None) => return Err(RemoteHDTError::MissingBlankNodeScope),
+        };
+        Ok(ParserOptions {
+            graph_policy: self.graph_policy,
+            contexts: &self.jsonld_contexts,
+            path,
+            dictionary_format: self.dictionary_format,
+            blank_node_policy: &self.blank_node_policy,
+            scope,
+        })
+    }
+
+    fn in_memory(&mut self, parsed: RdfParserResult) -> StorageResult<ParsedGraph> {
+        let (graph, dictionary, named_graphs) = parsed?;
+        self.dictionary = dictionary;
+        self.dimensionality = Dimensionality::new(&self.dictionary, &graph);
+        Ok(ParsedGraph::InMemory(graph, named_graphs))
+    }
+
+    fn write(
+        &mut self,
+        store: Arc<dyn ReadableWritableStorageTraits>,
+        graph: ParsedGraph,
+        chunking_strategy: ChunkingStrategy,
+        reference_system: ReferenceSystem,
+        threading_strategy: ThreadingStrategy,
+    ) -> StorageResult<&mut Self> {
+        // Create a group and write metadata to filesystem. The partitions of
+        // the objects are described in its attributes, so that they can be
+        // read back without listing the store
+        let partitions = self.dictionary.partitions();
+        let mut attributes = Map::new();
+        attributes.insert(
+            OBJECTS_ATTRIBUTE.into(),
+            partitions.iter().map(Partition::descriptor).collect(),
+        );
+        let blank_node_policy = match &self.blank_node_policy {
+            BlankNodePolicy::Keep => "keep",
+            BlankNodePolicy::Scope => "scope",
+            BlankNodePolicy::Skolemize(base) => {
+                attributes.insert(SKOLEM_BASE_ATTRIBUTE.into(), base.as_str().into());
+                "skolemize"
+            }
+        };
+        attributes.insert(BLANK_NODES_ATTRIBUTE.into(), blank_node_policy.into());
+        let group = GroupBuilder::new()
+            .attributes(attributes)
+            .build(store.clone(), "/group")?;
         group.store_metadata()?;
 
-        // TODO: rayon::ThreadPoolBuilder::new()
-        //     .num_threads(1)
-        //     .build_global()
-        //     .unwrap();
+        // The shards are encoded within a dedicated thread pool, so that the
+        // number of threads does not depend on the global one
+        let pool = ThreadPool::try_from(threading_strategy)?;
 
-        // 3. Import the RDF dump using `rdf-rs`
-        let graph = match RdfParser::parse(rdf_path, &reference_system) {
-            Ok((graph, dictionary)) => {
-                self.dictionary = dictionary;
-                self.dimensionality = Dimensionality::new(&self.dictionary, &graph);
-                graph
+        // 4. The Dictionary is stored as arrays of bytes next to the one
+        // storing the triples; that is, one for each of the front-coded sets.
+        // In the four-section format, the shared terms are stored in a section
+        // of their own, while the sections left empty are not stored
+        [SHARED_NAME, SUBJECTS_NAME, PREDICATES_NAME]
+            .into_iter()
+            .zip(self.dictionary.sections()?)
+            .filter_map(|(path, set)| Some((path, set?)))
+            .try_for_each(|(path, set)| set_to_zarr(store.clone(), path, &set))?;
+        // The objects are stored partition by partition, the integer literals
+        // being stored as an array of integers instead
+        for (index, partition) in partitions.iter().enumerate() {
+            let path = partition_path(index);
+            match &partition.terms {
+                Terms::Integers(values) => integers_to_zarr(store.clone(), &path, values)?,
+                terms => match terms.to_set()? {
+                    Some(set) => set_to_zarr(store.clone(), &path, &set)?,
+                    None => return Err(RemoteHDTError::DictionarySerialization),
+                },
+            }
+        }
+
+        let arr = self.create_array(
+            store.clone(),
+            ARRAY_NAME,
+            &self.dimensionality,
+            chunking_strategy,
+            &reference_system,
+        )?;
+        pool.install(|| match graph {
+            ParsedGraph::InMemory(graph, named_graphs) => {
+                self.layout.serialize(&arr, graph)?;
+                self.write_named_graphs(store, named_graphs, chunking_strategy, &reference_system)
+            }
+            ParsedGraph::Streaming(mut triples, named_graphs) => {
+                self.layout.serialize_triples(&arr, &mut triples)?;
+                if triples.error().is_some() {
+                    return Err(RemoteHDTError::TripleSerialization);
+                }
+                match named_graphs {
+                    Some(named_graphs) => self.stream_named_graphs(
+                        store,
+                        *named_graphs,
+                        chunking_strategy,
+                        &reference_system,
+                    ),
+                    None => Ok(()),
+                }
             }
-            Err(_) => return Err(RemoteHDTError::RdfParse),
+        })?;
+
+        Ok(self)
+    }
+
+    // 6. Every named graph is stored in an array of its own, which is named
+    // after the index of the graph in the set of names. This set is stored
+    // as yet another array of bytes, the same as the Dictionary
+    fn write_named_graphs(
+        &mut self,
+        store: Arc<dyn ReadableWritableStorageTraits>,
+        named_graphs: NamedGraphs,
+        chunking_strategy: ChunkingStrategy,
+        reference_system: &ReferenceSystem,
+    ) -> StorageResult<()> {
+        if named_graphs.is_empty() {
+            return Ok(());
+        }
+
+        let names = match Set::new(named_graphs.iter().map(|(name, _)| name)) {
+            Ok(names) => names,
+            Err(_) => return Err(RemoteHDTError::DictionarySerialization),
         };
+        set_to_zarr(store.clone(), GRAPHS_NAME, &names)?;
+        self.dictionary = mem::take(&mut self.dictionary).with_graphs(names);
+
+        for (index, (_, graph)) in named_graphs.into_iter().enumerate() {
+            let arr = self.create_array(
+                store.clone(),
+                &format!("{}/{}", PARTITIONS_NAME, index),
+                &Dimensionality::new(&self.dictionary, &graph),
+                chunking_strategy,
+                reference_system,
+            )?;
+            self.layout.serialize(&arr, graph)?;
+        }
+
+        Ok(())
+    }
+
+    fn stream_named_graphs(
+        &mut self,
+        store: Arc<dyn ReadableWritableStorageTraits>,
+        mut named_graphs: NamedGraphStream,
+        chunking_strategy: ChunkingStrategy,
+        reference_system: &ReferenceSystem,
+    ) -> StorageResult<()> {
+        set_to_zarr(store.clone(), GRAPHS_NAME, &named_graphs.names)?;
+        self.dictionary = mem::take(&mut self.dictionary).with_graphs(named_graphs.names);
+
+        let mut quads = named_graphs.quads.by_ref().peekable();
+        for (index, size) in named_graphs.sizes.into_iter().enumerate() {
+            let arr = self.create_array(
+                store.clone(),
+                &format!("{}/{}", PARTITIONS_NAME, index),
+                &Dimensionality::with_graph_size(&self.dictionary, Some(size)),
+                chunking_strategy,
+                reference_system,
+            )?;
+            // The quads are sorted by graph; hence, the ones of this graph are
+            // the following ones
+            let mut triples = std::iter::from_fn(|| {
+                quads
+                    .next_if(|quad| quad.0 == index as u32)
+                    .map(|(_, first, second, third)| (first, second, third))
+            });
+            self.layout.serialize_triples(&arr, &mut triples)?;
+        }
 
-        // 4. Build the structure of the Array; as such, several parameters of it are
-        // tweaked. Namely, the size of the array, the size of the chunks, the name
-        // of the different dimensions and the default values
-        let subjects = self.dictionary.subjects();
-        let predicates = self.dictionary.predicates();
-        let objects = self.dictionary.objects();
+        match named_graphs.quads.error() {
+            Some(_) => Err(RemoteHDTError::TripleSerialization),
+            None => Ok(()),
+        }
+    }
+
+    // 5. Build the structure of the Array; as such, several parameters of it are
+    // tweaked. Namely, the size of the array, the size of the chunks, the name
+    // of the different dimensions and the default values
+    fn create_array(
+        &self,
+        store: Arc<dyn ReadableWritableStorageTraits>,
+        path: &str,
+        dimensionality: &Dimensionality,
+        chunking_strategy: ChunkingStrategy,
+        reference_system: &ReferenceSystem,
+    ) -> StorageResult<Array<dyn ReadableWritableStorageTraits>> {
         let arr = ArrayBuilder::new(
-            self.layout.shape(&self.dimensionality),
+            self.layout.shape(dimensionality),
             self.layout.data_type(),
-            self.layout
-                .chunk_shape(chunking_strategy, &self.dimensionality),
+            self.layout.chunk_shape(chunking_strategy, dimensionality),
             self.layout.fill_value(),
         )
-        .dimension_names(self.layout.dimension_names(&reference_system))
-        .array_to_bytes_codec(self.layout.array_to_bytes_codec(&self.dimensionality)?)
+        .dimension_names(self.layout.dimension_names(reference_system))
+        .array_to_bytes_codec(self.layout.array_to_bytes_codec(dimensionality)?)
         .attributes({
             let mut attributes = Map::new();
-            attributes.insert("subjects".into(), rdf_to_value(subjects));
-            attributes.insert("predicates".into(), rdf_to_value(predicates));
-            attributes.insert("objects".into(), rdf_to_value(objects));
             attributes.insert("reference_system".into(), reference_system.as_ref().into());
             attributes
         })
-        .build(store.clone(), ARRAY_NAME)?;
+        .build(store, path)?;
 
         arr.store_metadata()?;
-        self.layout.serialize(&arr, graph)?;
-
-        let shape = ArraySubset::new_with_ranges(&[0..10, 1..2]);
-        arr.retrieve_array_subset_elements::<u32>(&shape).unwrap();
-
-        Ok(self)
+        Ok(arr)
     }
 
     pub fn load(
         &mut self,
         store: Backend<'_>,
-        // threading_strategy: ThreadingStrategy, TODO: implement this
+        threading_strategy: ThreadingStrategy,
     ) -> StorageResult<&mut Self> {
-        let store: Arc<dyn ReadableStorageTraits> = match store {
+        let (store, default_strategy): (Arc<dyn ReadableStorageTraits>, _) = match store {
             Backend::FileSystem(path) => {
                 let path = PathBuf::from_str(path)?;
 
                 match path.exists() {
                     false => return Err(RemoteHDTError::PathDoesNotExist),
-                    true => Arc::new(FilesystemStore::new(path)?),
+                    true => (
+                        Arc::new(FilesystemStore::new(path)?),
+                        DictionaryStrategy::Eager,
+                    ),
                 }
             }
-            Backend::HTTP(url) => Arc::new(HTTPStore::new(url)?),
+            Backend::HTTP(url) => (Arc::new(HTTPStore::new(url)?), DictionaryStrategy::Lazy),
+            Backend::Memory(store) => (store, DictionaryStrategy::Eager),
+            Backend::Store(store) => (store, DictionaryStrategy::Eager),
+            Backend::AsyncStore(_) => return Err(RemoteHDTError::AsyncBackend),
         };
 
-        let arr = Array::new(store, ARRAY_NAME)?;
-        let dictionary = self.layout.retrieve_attributes(&arr)?;
-        self.dictionary = dictionary;
+        let arr = Array::new(store.clone(), ARRAY_NAME)?;
+        let reference_system = self.layout.retrieve_attributes(arr.attributes())?;
+        let strategy = self
+            .dictionary_strategy
+            .as_ref()
+            .unwrap_or(&default_strategy);
+        // In the four-section format, all the subjects or all the objects may
+        // be shared, in which case their own section is not stored
+        let four_section = store.get(&metadata_key(SHARED_NAME)?)?.is_some();
+        let section = |path: &str, optional: bool| -> StorageResult<Terms> {
+            if optional && store.get(&metadata_key(path)?)?.is_none() {
+                return Ok(Terms::Empty);
+            }
+            match strategy {
+                DictionaryStrategy::Eager => Ok(zarr_to_set(store.clone(), path)?.into()),
+                // Only the pointers to the buckets of the front-coded sets are
+                // retrieved, while the terms will be fetched when they are needed
+                DictionaryStrategy::Lazy => Ok(LazySet::new(store.clone(), path)?.into()),
+            }
+        };
+        let group = Group::new(store.clone(), "/group")?;
+        self.blank_node_policy = blank_node_policy(group.attributes());
+        let objects = object_partitions(group.attributes())?
+            .into_iter()
+            .map(|(path, kind, integers)| {
+                let terms = match integers {
+                    true => Terms::Integers(Arc::new(zarr_to_integers(store.clone(), &path)?)),
+                    false => section(&path, false)?,
+                };
+                Ok(Partition { kind, terms })
+            })
+            .collect::<StorageResult<Vec<_>>>()?;
+        self.dictionary = Dictionary::from_sections(
+            reference_system,
+            section(SHARED_NAME, true)?,
+            section(SUBJECTS_NAME, four_section)?,
+            section(PREDICATES_NAME, false)?,
+            objects,
+        );
+        if store.get(&StoreKey::new(GRAPHS_METADATA)?)?.is_some() {
+            let graphs = zarr_to_set(store.clone(), GRAPHS_NAME)?;
+            self.dictionary = mem::take(&mut self.dictionary).with_graphs(graphs);
+        }
+        self.reference_system = self.dictionary.get_reference_system();
+        self.dimensionality = Dimensionality::new(&self.dictionary, &Graph::default());
+        self.graph = None;
+
+        let partitions = (0..self.dictionary.graphs_size())
+            .map(|index| Array::new(store.clone(), &format!("{}/{}", PARTITIONS_NAME, index)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match self.serialization {
+            Serialization::Zarr => {
+                self.array = Some(arr);
+                self.partitions = partitions;
+            }
+            Serialization::Sparse => {
+                let pool = ThreadPool::try_from(threading_strategy)?;
+                pool.install(|| {
+                    self.sparse_array = Some(self.layout.parse(&arr, &self.dimensionality)?);
+                    self.sparse_partitions = partitions
+                        .iter()
+                        .map(|partition| self.layout.parse(partition, &self.dimensionality))
+                        .collect::<StorageResult<Vec<_>>>()?;
+                    Ok::<_, RemoteHDTError>(())
+                })?
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// The asynchronous counterpart of [`Storage::load`]. The dataset is read
+    /// through an asynchronous store; hence, it can be queried concurrently
+    /// using [`ops::AsyncOps`] without blocking the threads of the runtime.
+    /// The Dictionary is always retrieved eagerly.
+    pub async fn async_load(&mut self, store: Backend<'_>) -> StorageResult<&mut Self> {
+        let store: Arc<dyn AsyncReadableStorageTraits> = match store {
+            Backend::FileSystem(path) => {
+                let path = PathBuf::from_str(path)?;
+
+                match path.exists() {
+                    false => return Err(RemoteHDTError::PathDoesNotExist),
+                    true => Arc::new(AsyncObjectStore::new(LocalFileSystem::new_with_prefix(
+                        path,
+                    )?)),
+                }
+            }
+            Backend::HTTP(url) => Arc::new(AsyncObjectStore::new(
+                HttpBuilder::new().with_url(url).build()?,
+            )),
+            Backend::Memory(_) | Backend::Store(_) => return Err(RemoteHDTError::SyncBackend),
+            Backend::AsyncStore(store) => store,
+        };
+
+        let arr = Array::async_new(store.clone(), ARRAY_NAME).await?;
+        let reference_system = self.layout.retrieve_attributes(arr.attributes())?;
+        let four_section = store.get(&metadata_key(SHARED_NAME)?).await?.is_some();
+        let group = Group::async_new(store.clone(), "/group").await?;
+        self.blank_node_policy = blank_node_policy(group.attributes());
+        let objects = try_join_all(object_partitions(group.attributes())?.into_iter().map(
+            |(path, kind, integers)| {
+                let store = store.clone();
+                async move {
+                    let terms = match integers {
+                        true => {
+                            Terms::Integers(Arc::new(async_zarr_to_integers(store, &path).await?))
+                        }
+                        false => async_section(store, &path, false).await?,
+                    };
+                    Ok::<_, RemoteHDTError>(Partition { kind, terms })
+                }
+            },
+        ));
+        let (shared, subjects, predicates, objects) = try_join!(
+            async_section(store.clone(), SHARED_NAME, true),
+            async_section(store.clone(), SUBJECTS_NAME, four_section),
+            async_section(store.clone(), PREDICATES_NAME, false),
+            objects,
+        )?;
+        self.dictionary =
+            Dictionary::from_sections(reference_system, shared, subjects, predicates, objects);
+        if store.get(&StoreKey::new(GRAPHS_METADATA)?).await?.is_some() {
+            let graphs = async_zarr_to_set(store.clone(), GRAPHS_NAME).await?;
+            self.dictionary = mem::take(&mut self.dictionary).with_graphs(graphs);
+        }
         self.reference_system = self.dictionary.get_reference_system();
         self.dimensionality = Dimensionality::new(&self.dictionary, &Graph::default());
+        self.graph = None;
+
+        let mut partitions = Vec::with_capacity(self.dictionary.graphs_size());
+        for index in 0..self.dictionary.graphs_size() {
+            let path = format!("{}/{}", PARTITIONS_NAME, index);
+            partitions.push(Array::async_new(store.clone(), &path).await?);
+        }
 
         match self.serialization {
-            Serialization::Zarr => self.array = Some(arr),
+            Serialization::Zarr => {
+                self.async_array = Some(arr);
+                self.async_partitions = partitions;
+            }
             Serialization::Sparse => {
-                self.sparse_array = Some(self.layout.parse(&arr, &self.dimensionality)?)
+                self.sparse_array =
+                    Some(self.layout.async_parse(&arr, &self.dimensionality).await?);
+                self.sparse_partitions = Vec::with_capacity(partitions.len());
+                for partition in partitions.iter() {
+                    let partition = self
+                        .layout
+                        .async_parse(partition, &self.dimensionality)
+                        .await?;
+                    self.sparse_partitions.push(partition);
+                }
             }
         }
 
         Ok(self)
     }
+
+    /// Writes the triples of the loaded dataset, or those of the selected
+    /// graph, into the provided writer, which is returned once flushed. The
+    /// Zarr array is decoded shard by shard; hence, only the triples of a
+    /// single shard are kept in memory at a time, except for HDT, whose
+    /// triples have to be sorted before being written. These are sorted on
+    /// disk if [`MemoryStrategy::Streaming`] is set, and the array is
+    /// traversed twice, as the terms that are used must be written first.
+    /// Note that those terms are still kept in memory while exporting to HDT.
+    ///
+    /// # Errors
+    /// Returns [`RemoteHDTError::NotSupportedExport`] if the format is neither
+    /// N-Triples, Turtle nor HDT, and [`OpsError::EmptyArray`] if the dataset
+    /// has not been loaded synchronously.
+    pub fn export<W: Write>(&self, writer: W, format: RdfFormat) -> StorageResult<W> {
+        match format {
+            RdfFormat::Hdt => {
+                let capacity = match self.memory_strategy {
+                    MemoryStrategy::InMemory => usize::MAX,
+                    MemoryStrategy::Streaming(buffer_size) => buffer_size,
+                };
+                Hdt::write(writer, &self.dictionary, capacity, |visit| {
+                    self.for_each_triple(|first, second, third| {
+                        let (subject, predicate, object) =
+                            self.reference_system.to_spo(first, second, third);
+                        visit(subject, predicate, object)
+                    })
+                })
+            }
+            format => {
+                let mut formatter = RdfFormatter::new(writer, format)?;
+                self.for_each_triple(|first, second, third| {
+                    formatter.format(&self.decode_triple(first, second, third)?)
+                })?;
+                formatter.finish()
+            }
+        }
+    }
+
+    /// Visits the triples of the selected graph, given by their first, second
+    /// and third terms
+    fn for_each_triple(
+        &self,
+        mut visit: impl FnMut(usize, usize, usize) -> StorageResult<()>,
+    ) -> StorageResult<()> {
+        match self.serialization {
+            Serialization::Zarr => {
+                let array = match self.selected_array() {
+                    Some(array) => array,
+                    None => return Err(OpsError::EmptyArray.into()),
+                };
+                let number_of_shards = match array.chunk_grid_shape() {
+                    Some(chunk_grid) => chunk_grid[0],
+                    None => 0,
+                };
+                for shard in 0..number_of_shards {
+                    for (first, second, third) in
+                        self.layout
+                            .retrieve_shard(array, shard, &self.dimensionality)?
+                    {
+                        visit(first, second, third)?;
+                    }
+                }
+            }
+            // The matrix is traversed by rows, so that the triples sharing
+            // their first term are visited one after the other
+            Serialization::Sparse => match self.selected_sparse_array() {
+                Some(array) => {
+                    for (&second, (first, third)) in array.to_csr().iter() {
+                        visit(first, second, third)?;
+                    }
+                }
+                None => return Err(OpsError::EmptySparseArray.into()),
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// The store in which a dataset is going to be serialized, which must be
+/// writable and empty
+fn writable_store(store: Backend<'_>) -> StorageResult<Arc<dyn ReadableWritableStorageTraits>> {
+    match store {
+        Backend::FileSystem(path) => {
+            let path = PathBuf::from_str(path)?;
+
+            match path.exists() {
+                true => Err(RemoteHDTError::PathExists),
+                false => Ok(Arc::new(FilesystemStore::new(path)?)),
+            }
+        }
+        Backend::HTTP(_) => Err(RemoteHDTError::ReadOnlyBackend),
+        // Neither can a dataset be serialized on top of an existing one when
+        // using the rest of the stores
+        Backend::Memory(store) => empty_store(store),
+        Backend::Store(store) => empty_store(store),
+        Backend::AsyncStore(_) => Err(RemoteHDTError::AsyncBackend),
+    }
+}
+
+fn empty_store(
+    store: Arc<dyn ReadableWritableStorageTraits>,
+) -> StorageResult<Arc<dyn ReadableWritableStorageTraits>> {
+    match store.get(&StoreKey::new(GROUP_METADATA)?)? {
+        Some(_) => Err(RemoteHDTError::PathExists),
+        None => Ok(store),
+    }
+}
+
+/// The key of the metadata of an array, which tells whether it is stored
+fn metadata_key(path: &str) -> StorageResult<StoreKey> {
+    Ok(StoreKey::new(format!(
+        "{}/zarr.json",
+        path.trim_start_matches('/')
+    ))?)
+}
+
+/// The array storing a partition of the objects
+fn partition_path(index: usize) -> String {
+    format!("{}/{}", OBJECTS_NAME, index)
+}
+
+/// The array in which a partition of the objects is stored, its kind, and
+/// whether it is stored as integers
+type PartitionDescriptor = (String, Option<TermKind>, bool);
+
+/// Reads the partitions of the objects from the attributes of the group. The
+/// datasets serialized before the objects were partitioned store all of them
+/// in a single array, which is read as a partition of no kind so that the
+/// indices of the objects remain the ones of their triples.
+fn object_partitions(attributes: &Map<String, Value>) -> StorageResult<Vec<PartitionDescriptor>> {
+    match attributes.get(OBJECTS_ATTRIBUTE).and_then(Value::as_array) {
+        Some(descriptors) => descriptors
+            .iter()
+            .enumerate()
+            .map(|(index, descriptor)| {
+                let (kind, integers) = Partition::from_descriptor(descriptor)
+                    .ok_or(RemoteHDTError::DictionaryDeserialization)?;
+                Ok((partition_path(index), kind, integers))
+            })
+            .collect(),
+        None => Ok(vec![(OBJECTS_NAME.to_string(), None, false)]),
+    }
+}
+
+/// Reads how the blank nodes of a dataset were labeled from the attributes of
+/// the group; those datasets in which it is not recorded kept them as they were
+fn blank_node_policy(attributes: &Map<String, Value>) -> BlankNodePolicy {
+    let skolem_base = attributes
+        .get(SKOLEM_BASE_ATTRIBUTE)
+        .and_then(Value::as_str)
+        .map(String::from);
+    match (
+        attributes
+            .get(BLANK_NODES_ATTRIBUTE)
+            .and_then(Value::as_str),
+        skolem_base,
+    ) {
+        (Some("scope"), _) => BlankNodePolicy::Scope,
+        (Some("skolemize"), Some(base)) => BlankNodePolicy::Skolemize(base),
+        _ => BlankNodePolicy::Keep,
+    }
+}
+
+/// Retrieves a section of the Dictionary through an asynchronous store; if it
+/// is optional, a missing section is considered empty
+async fn async_section(
+    store: Arc<dyn AsyncReadableStorageTraits>,
+    path: &str,
+    optional: bool,
+) -> StorageResult<Terms> {
+    if optional && store.get(&metadata_key(path)?).await?.is_none() {
+        return Ok(Terms::Empty);
+    }
+    Ok(async_zarr_to_set(store, path).await?.into())
 }
@@ -1,4 +1,11 @@
+use async_trait::async_trait;
+use std::ops::Range;
+use zarrs::array_subset::ArraySubset;
+
+use crate::engine::AsyncEngineStrategy;
 use crate::engine::EngineStrategy;
+use crate::engine::Query;
+use crate::error::EngineError;
 use crate::error::OpsError;
 
 use super::params::ReferenceSystem;
@@ -7,6 +14,8 @@ use super::Storage;
 use super::ZarrArray;
 
 pub type OpsResult = Result<OpsFormat, OpsError>;
+pub type StringTriple = (String, String, String);
+pub type TriplesResult = Result<Vec<StringTriple>, OpsError>;
 
 pub enum OpsFormat {
     SparseArray(ZarrArray),
@@ -17,32 +26,96 @@ pub trait Ops {
     fn get_subject(&self, subject: &str) -> OpsResult;
     fn get_predicate(&self, predicate: &str) -> OpsResult;
     fn get_object(&self, object: &str) -> OpsResult;
+    fn get_subject_predicate(&self, subject: &str, predicate: &str) -> OpsResult;
+    fn get_subject_object(&self, subject: &str, object: &str) -> OpsResult;
+    fn get_predicate_object(&self, predicate: &str, object: &str) -> OpsResult;
+    fn contains(&self, subject: &str, predicate: &str, object: &str) -> Result<bool, OpsError>;
+    fn get_triples(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&str>,
+    ) -> TriplesResult;
+    /// Retrieves the triples whose object is within a range of indices of the
+    /// Dictionary, such as the one of the literals or the one of the integers
+    /// within some bounds; see [`crate::dictionary::Dictionary::literals`].
+    fn get_triples_in_range(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        objects: Range<usize>,
+    ) -> TriplesResult;
+}
+
+/// The asynchronous counterpart of [`Ops`], for those datasets loaded using
+/// [`Storage::async_load`]. Queries over sparse arrays are answered in memory;
+/// hence, they are resolved as in [`Ops`].
+#[async_trait]
+pub trait AsyncOps {
+    async fn async_get_subject(&self, subject: &str) -> OpsResult;
+    async fn async_get_predicate(&self, predicate: &str) -> OpsResult;
+    async fn async_get_object(&self, object: &str) -> OpsResult;
+    async fn async_get_subject_predicate(&self, subject: &str, predicate: &str) -> OpsResult;
+    async fn async_get_subject_object(&self, subject: &str, object: &str) -> OpsResult;
+    async fn async_get_predicate_object(&self, predicate: &str, object: &str) -> OpsResult;
+    async fn async_contains(
+        &self,
+        subject: &str,
+        predicate: &str,
+        object: &str,
+    ) -> Result<bool, OpsError>;
+    async fn async_get_triples(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&str>,
+    ) -> TriplesResult;
+    async fn async_get_triples_in_range(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        objects: Range<usize>,
+    ) -> TriplesResult;
 }
 
 impl<C> Ops for Storage<C> {
     fn get_subject(&self, subject: &str) -> OpsResult {
-        let index = match self.dictionary.get_subject_idx(subject) {
-            Some(index) => index,
-            None => return Err(OpsError::SubjectNotFound),
+        self.run(self.subject_query(subject)?)
+    }
+
+    fn get_predicate(&self, predicate: &str) -> OpsResult {
+        self.run(self.predicate_query(predicate)?)
+    }
+
+    fn get_object(&self, object: &str) -> OpsResult {
+        self.run(self.object_query(object)?)
+    }
+
+    fn get_subject_predicate(&self, subject: &str, predicate: &str) -> OpsResult {
+        self.run(self.subject_predicate_query(subject, predicate)?)
+    }
+
+    fn get_subject_object(&self, subject: &str, object: &str) -> OpsResult {
+        self.run(self.subject_object_query(subject, object)?)
+    }
+
+    fn get_predicate_object(&self, predicate: &str, object: &str) -> OpsResult {
+        self.run(self.predicate_object_query(predicate, object)?)
+    }
+
+    fn contains(&self, subject: &str, predicate: &str, object: &str) -> Result<bool, OpsError> {
+        let (first, second, third) = match self.stored_triple(subject, predicate, object)? {
+            Some(triple) => triple,
+            None => return Ok(false),
         };
 
         let ans = match self.serialization {
-            Serialization::Zarr => match &self.array {
-                Some(array) => OpsFormat::Zarr(match self.reference_system {
-                    ReferenceSystem::SPO | ReferenceSystem::SOP => array.get_first_term(index)?,
-                    ReferenceSystem::PSO | ReferenceSystem::OSP => array.get_second_term(index)?,
-                    ReferenceSystem::POS | ReferenceSystem::OPS => array.get_third_term(index)?,
-                }),
+            Serialization::Zarr => match self.selected_array() {
+                Some(array) => array.contains(first, second, third)?,
                 None => return Err(OpsError::EmptyArray),
             },
-            Serialization::Sparse => match &self.sparse_array {
-                Some(array) => OpsFormat::SparseArray(match self.reference_system {
-                    ReferenceSystem::SPO | ReferenceSystem::SOP => array.get_first_term(index)?,
-                    ReferenceSystem::PSO | ReferenceSystem::OSP => array.get_second_term(index)?,
-                    ReferenceSystem::POS | ReferenceSystem::OPS => {
-                        array.get_third_term(index).unwrap()
-                    }
-                }),
+            Serialization::Sparse => match self.selected_sparse_array() {
+                Some(array) => array.contains(first, second, third)?,
                 None => return Err(OpsError::EmptySparseArray),
             },
         };
@@ -50,27 +123,175 @@ impl<C> Ops for Storage<C> {
         Ok(ans)
     }
 
-    fn get_predicate(&self, predicate: &str) -> OpsResult {
-        let index = match self.dictionary.get_predicate_idx(predicate) {
-            Some(index) => index,
-            None => return Err(OpsError::PredicateNotFound),
+    fn get_triples(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&str>,
+    ) -> TriplesResult {
+        let ans = match (subject, predicate, object) {
+            (Some(subject), Some(predicate), Some(object)) => {
+                return match self.contains(subject, predicate, object)? {
+                    true => Ok(vec![(
+                        subject.to_string(),
+                        predicate.to_string(),
+                        object.to_string(),
+                    )]),
+                    false => Ok(Vec::new()),
+                }
+            }
+            _ => match self.pattern_query(subject, predicate, object)? {
+                Some(query) => self.run(query)?,
+                None => self.get_all()?,
+            },
         };
 
+        self.decode_pattern(subject, predicate, object, ans)
+    }
+
+    fn get_triples_in_range(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        objects: Range<usize>,
+    ) -> TriplesResult {
+        let bounds = self.bounds(subject, predicate, objects)?;
+        let cells = match self.serialization {
+            Serialization::Zarr => match self.selected_array() {
+                Some(array) => bounds.cells(
+                    array
+                        .retrieve_array_subset_elements::<u32>(&bounds.subset())
+                        .map_err(EngineError::from)?,
+                ),
+                None => return Err(OpsError::EmptyArray),
+            },
+            Serialization::Sparse => match self.selected_sparse_array() {
+                Some(array) => bounds.entries(array),
+                None => return Err(OpsError::EmptySparseArray),
+            },
+        };
+
+        cells
+            .into_iter()
+            .map(|(first, second, third)| self.decode_triple(first, second, third))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<C> AsyncOps for Storage<C> {
+    async fn async_get_subject(&self, subject: &str) -> OpsResult {
+        self.async_run(self.subject_query(subject)?).await
+    }
+
+    async fn async_get_predicate(&self, predicate: &str) -> OpsResult {
+        self.async_run(self.predicate_query(predicate)?).await
+    }
+
+    async fn async_get_object(&self, object: &str) -> OpsResult {
+        self.async_run(self.object_query(object)?).await
+    }
+
+    async fn async_get_subject_predicate(&self, subject: &str, predicate: &str) -> OpsResult {
+        self.async_run(self.subject_predicate_query(subject, predicate)?)
+            .await
+    }
+
+    async fn async_get_subject_object(&self, subject: &str, object: &str) -> OpsResult {
+        self.async_run(self.subject_object_query(subject, object)?)
+            .await
+    }
+
+    async fn async_get_predicate_object(&self, predicate: &str, object: &str) -> OpsResult {
+        self.async_run(self.predicate_object_query(predicate, object)?)
+            .await
+    }
+
+    async fn async_contains(
+        &self,
+        subject: &str,
+        predicate: &str,
+        object: &str,
+    ) -> Result<bool, OpsError> {
+        let array = match (&self.serialization, self.selected_async_array()) {
+            (Serialization::Sparse, _) => return self.contains(subject, predicate, object),
+            (Serialization::Zarr, Some(array)) => array,
+            (Serialization::Zarr, None) => return Err(OpsError::EmptyArray),
+        };
+
+        match self.stored_triple(subject, predicate, object)? {
+            Some((first, second, third)) => Ok(array.contains(first, second, third).await?),
+            None => Ok(false),
+        }
+    }
+
+    async fn async_get_triples(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&str>,
+    ) -> TriplesResult {
+        let ans = match (subject, predicate, object) {
+            (Some(subject), Some(predicate), Some(object)) => {
+                return match self.async_contains(subject, predicate, object).await? {
+                    true => Ok(vec![(
+                        subject.to_string(),
+                        predicate.to_string(),
+                        object.to_string(),
+                    )]),
+                    false => Ok(Vec::new()),
+                }
+            }
+            _ => match self.pattern_query(subject, predicate, object)? {
+                Some(query) => self.async_run(query).await?,
+                None => self.async_get_all().await?,
+            },
+        };
+
+        self.decode_pattern(subject, predicate, object, ans)
+    }
+
+    async fn async_get_triples_in_range(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        objects: Range<usize>,
+    ) -> TriplesResult {
+        let bounds = self.bounds(subject, predicate, objects)?;
+        let cells = match (&self.serialization, self.selected_async_array()) {
+            (Serialization::Sparse, _) => match self.selected_sparse_array() {
+                Some(array) => bounds.entries(array),
+                None => return Err(OpsError::EmptySparseArray),
+            },
+            (Serialization::Zarr, Some(array)) => bounds.cells(
+                array
+                    .async_retrieve_array_subset_elements::<u32>(&bounds.subset())
+                    .await
+                    .map_err(EngineError::from)?,
+            ),
+            (Serialization::Zarr, None) => return Err(OpsError::EmptyArray),
+        };
+
+        cells
+            .into_iter()
+            .map(|(first, second, third)| self.decode_triple(first, second, third))
+            .collect()
+    }
+}
+
+// Both the synchronous and the asynchronous queries are resolved into a call
+// to an engine by the following methods; hence, they only differ in how the
+// array of the selected graph is retrieved
+impl<C> Storage<C> {
+    /// Runs the query against the array of the selected graph
+    fn run(&self, query: Query) -> OpsResult {
         let ans = match self.serialization {
-            Serialization::Zarr => match &self.array {
-                Some(array) => OpsFormat::Zarr(match self.reference_system {
-                    ReferenceSystem::PSO | ReferenceSystem::POS => array.get_first_term(index)?,
-                    ReferenceSystem::SPO | ReferenceSystem::OPS => array.get_second_term(index)?,
-                    ReferenceSystem::SOP | ReferenceSystem::OSP => array.get_third_term(index)?,
-                }),
+            Serialization::Zarr => match self.selected_array() {
+                Some(array) => OpsFormat::Zarr(query.run(array)?),
                 None => return Err(OpsError::EmptyArray),
             },
-            Serialization::Sparse => match &self.sparse_array {
-                Some(array) => OpsFormat::SparseArray(match self.reference_system {
-                    ReferenceSystem::PSO | ReferenceSystem::POS => array.get_first_term(index)?,
-                    ReferenceSystem::SPO | ReferenceSystem::OPS => array.get_second_term(index)?,
-                    ReferenceSystem::SOP | ReferenceSystem::OSP => array.get_third_term(index)?,
-                }),
+            Serialization::Sparse => match self.selected_sparse_array() {
+                Some(array) => OpsFormat::SparseArray(query.run(array)?),
                 None => return Err(OpsError::EmptySparseArray),
             },
         };
@@ -78,31 +299,387 @@ impl<C> Ops for Storage<C> {
         Ok(ans)
     }
 
-    fn get_object(&self, object: &str) -> OpsResult {
-        let index = match self.dictionary.get_object_idx(object) {
-            Some(index) => index,
-            None => return Err(OpsError::ObjectNotFound),
+    /// Runs the query against the array of the selected graph, which is
+    /// retrieved asynchronously unless it is a sparse array
+    async fn async_run(&self, query: Query) -> OpsResult {
+        match (&self.serialization, self.selected_async_array()) {
+            (Serialization::Sparse, _) => self.run(query),
+            (Serialization::Zarr, Some(array)) => {
+                Ok(OpsFormat::Zarr(query.async_run(array).await?))
+            }
+            (Serialization::Zarr, None) => Err(OpsError::EmptyArray),
+        }
+    }
+
+    /// The query for a pattern with one or two of its terms bound. There is
+    /// none otherwise, as either the whole array is read, or the triple is
+    /// looked up using `contains`
+    fn pattern_query(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&str>,
+    ) -> Result<Option<Query>, OpsError> {
+        let query = match (subject, predicate, object) {
+            (Some(subject), Some(predicate), None) => {
+                self.subject_predicate_query(subject, predicate)
+            }
+            (Some(subject), None, Some(object)) => self.subject_object_query(subject, object),
+            (None, Some(predicate), Some(object)) => self.predicate_object_query(predicate, object),
+            (Some(subject), None, None) => self.subject_query(subject),
+            (None, Some(predicate), None) => self.predicate_query(predicate),
+            (None, None, Some(object)) => self.object_query(object),
+            _ => return Ok(None),
         };
+        query.map(Some)
+    }
+
+    fn subject_query(&self, subject: &str) -> Result<Query, OpsError> {
+        let index = self.subject_idx(subject)?;
+        Ok(match self.reference_system {
+            ReferenceSystem::SPO | ReferenceSystem::SOP => Query::First(index),
+            ReferenceSystem::PSO | ReferenceSystem::OSP => Query::Second(index),
+            ReferenceSystem::POS | ReferenceSystem::OPS => Query::Third(index),
+        })
+    }
+
+    fn predicate_query(&self, predicate: &str) -> Result<Query, OpsError> {
+        let index = self.predicate_idx(predicate)?;
+        Ok(match self.reference_system {
+            ReferenceSystem::PSO | ReferenceSystem::POS => Query::First(index),
+            ReferenceSystem::SPO | ReferenceSystem::OPS => Query::Second(index),
+            ReferenceSystem::SOP | ReferenceSystem::OSP => Query::Third(index),
+        })
+    }
+
+    fn object_query(&self, object: &str) -> Result<Query, OpsError> {
+        let index = self.object_idx(object)?;
+        Ok(match self.reference_system {
+            ReferenceSystem::OPS | ReferenceSystem::OSP => Query::First(index),
+            ReferenceSystem::SOP | ReferenceSystem::POS => Query::Second(index),
+            ReferenceSystem::SPO | ReferenceSystem::PSO => Query::Third(index),
+        })
+    }
+
+    fn subject_predicate_query(&self, subject: &str, predicate: &str) -> Result<Query, OpsError> {
+        let (subject, predicate) = (self.subject_idx(subject)?, self.predicate_idx(predicate)?);
+        Ok(match self.reference_system {
+            ReferenceSystem::SPO => Query::FirstAndSecond(subject, predicate),
+            ReferenceSystem::SOP => Query::FirstAndThird(subject, predicate),
+            ReferenceSystem::PSO => Query::FirstAndSecond(predicate, subject),
+            ReferenceSystem::POS => Query::FirstAndThird(predicate, subject),
+            ReferenceSystem::OSP => Query::SecondAndThird(subject, predicate),
+            ReferenceSystem::OPS => Query::SecondAndThird(predicate, subject),
+        })
+    }
+
+    fn subject_object_query(&self, subject: &str, object: &str) -> Result<Query, OpsError> {
+        let (subject, object) = (self.subject_idx(subject)?, self.object_idx(object)?);
+        Ok(match self.reference_system {
+            ReferenceSystem::SPO => Query::FirstAndThird(subject, object),
+            ReferenceSystem::SOP => Query::FirstAndSecond(subject, object),
+            ReferenceSystem::PSO => Query::SecondAndThird(subject, object),
+            ReferenceSystem::POS => Query::SecondAndThird(object, subject),
+            ReferenceSystem::OSP => Query::FirstAndSecond(object, subject),
+            ReferenceSystem::OPS => Query::FirstAndThird(object, subject),
+        })
+    }
+
+    fn predicate_object_query(&self, predicate: &str, object: &str) -> Result<Query, OpsError> {
+        let (predicate, object) = (self.predicate_idx(predicate)?, self.object_idx(object)?);
+        Ok(match self.reference_system {
+            ReferenceSystem::SPO => Query::SecondAndThird(predicate, object),
+            ReferenceSystem::SOP => Query::SecondAndThird(object, predicate),
+            ReferenceSystem::PSO => Query::FirstAndThird(predicate, object),
+            ReferenceSystem::POS => Query::FirstAndSecond(predicate, object),
+            ReferenceSystem::OSP => Query::FirstAndThird(object, predicate),
+            ReferenceSystem::OPS => Query::FirstAndSecond(object, predicate),
+        })
+    }
+
+    /// The positions in which the terms of the triple would be stored. A
+    /// triple whose terms are not in the Dictionary cannot be stored in the
+    /// array; hence, there is no need for us to look it up
+    fn stored_triple(
+        &self,
+        subject: &str,
+        predicate: &str,
+        object: &str,
+    ) -> Result<Option<(usize, usize, usize)>, OpsError> {
+        let (subject, predicate, object) = match (
+            self.dictionary.try_get_subject_idx(subject)?,
+            self.dictionary.try_get_predicate_idx(predicate)?,
+            self.dictionary.try_get_object_idx(object)?,
+        ) {
+            (Some(subject), Some(predicate), Some(object)) => (subject, predicate, object),
+            _ => return Ok(None),
+        };
+
+        // We sort the terms so they match the positions in which they are
+        // stored; that is, (first term, second term, third term)
+        Ok(Some(match self.reference_system {
+            ReferenceSystem::SPO => (subject, predicate, object),
+            ReferenceSystem::SOP => (subject, object, predicate),
+            ReferenceSystem::PSO => (predicate, subject, object),
+            ReferenceSystem::POS => (predicate, object, subject),
+            ReferenceSystem::OSP => (object, subject, predicate),
+            ReferenceSystem::OPS => (object, predicate, subject),
+        }))
+    }
+
+    fn subject_idx(&self, subject: &str) -> Result<usize, OpsError> {
+        match self.dictionary.try_get_subject_idx(subject)? {
+            Some(index) => Ok(index),
+            None => Err(OpsError::SubjectNotFound),
+        }
+    }
+
+    fn predicate_idx(&self, predicate: &str) -> Result<usize, OpsError> {
+        match self.dictionary.try_get_predicate_idx(predicate)? {
+            Some(index) => Ok(index),
+            None => Err(OpsError::PredicateNotFound),
+        }
+    }
+
+    fn object_idx(&self, object: &str) -> Result<usize, OpsError> {
+        match self.dictionary.try_get_object_idx(object)? {
+            Some(index) => Ok(index),
+            None => Err(OpsError::ObjectNotFound),
+        }
+    }
+}
+
+impl<C> Storage<C> {
+    /// Decodes the result of a query into the triples matching the pattern.
+    fn decode_pattern(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        object: Option<&str>,
+        ans: OpsFormat,
+    ) -> TriplesResult {
+        // The terms have already been found in the Dictionary when running the
+        // query, so we can safely look them up once again. Knowing which of the
+        // first and third terms are bound tells us how the Zarr result is laid
+        // out: a cell, a row, a column or the whole flattened array
+        let subject = match subject {
+            Some(subject) => self.dictionary.try_get_subject_idx(subject)?,
+            None => None,
+        };
+        let predicate = match predicate {
+            Some(predicate) => self.dictionary.try_get_predicate_idx(predicate)?,
+            None => None,
+        };
+        let object = match object {
+            Some(object) => self.dictionary.try_get_object_idx(object)?,
+            None => None,
+        };
+        let (first, third) = match self.reference_system {
+            ReferenceSystem::SPO => (subject, object),
+            ReferenceSystem::SOP => (subject, predicate),
+            ReferenceSystem::PSO => (predicate, object),
+            ReferenceSystem::POS => (predicate, subject),
+            ReferenceSystem::OSP => (object, predicate),
+            ReferenceSystem::OPS => (object, subject),
+        };
+
+        let coordinates = match ans {
+            OpsFormat::SparseArray(matrix) => matrix
+                .iter()
+                .map(|(&second_term, (first_term, third_term))| {
+                    (first_term, second_term, third_term)
+                })
+                .collect::<Vec<_>>(),
+            OpsFormat::Zarr(values) => {
+                let columns = self.dimensionality.third_term_size;
+                values
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &second_term)| second_term != 0)
+                    .map(|(idx, &second_term)| {
+                        let second_term = second_term as usize;
+                        match (first, third) {
+                            (Some(first), Some(third)) => (first, second_term, third),
+                            (Some(first), None) => (first, second_term, idx),
+                            (None, Some(third)) => (idx, second_term, third),
+                            (None, None) => (idx / columns, second_term, idx % columns),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        coordinates
+            .into_iter()
+            .map(|(first, second, third)| self.decode_triple(first, second, third))
+            .collect()
+    }
 
+    fn get_all(&self) -> OpsResult {
         let ans = match self.serialization {
-            Serialization::Zarr => match &self.array {
-                Some(array) => OpsFormat::Zarr(match self.reference_system {
-                    ReferenceSystem::OPS | ReferenceSystem::OSP => array.get_first_term(index)?,
-                    ReferenceSystem::SOP | ReferenceSystem::POS => array.get_second_term(index)?,
-                    ReferenceSystem::SPO | ReferenceSystem::PSO => array.get_third_term(index)?,
-                }),
+            Serialization::Zarr => match self.selected_array() {
+                Some(array) => {
+                    let shape = ArraySubset::new_with_shape(array.shape().to_vec());
+                    OpsFormat::Zarr(
+                        array
+                            .retrieve_array_subset_elements::<u32>(&shape)
+                            .map_err(EngineError::from)?,
+                    )
+                }
                 None => return Err(OpsError::EmptyArray),
             },
-            Serialization::Sparse => match &self.sparse_array {
-                Some(array) => OpsFormat::SparseArray(match self.reference_system {
-                    ReferenceSystem::OPS | ReferenceSystem::OSP => array.get_first_term(index)?,
-                    ReferenceSystem::SOP | ReferenceSystem::POS => array.get_second_term(index)?,
-                    ReferenceSystem::SPO | ReferenceSystem::PSO => array.get_third_term(index)?,
-                }),
+            Serialization::Sparse => match self.selected_sparse_array() {
+                Some(array) => OpsFormat::SparseArray(array.to_owned()),
                 None => return Err(OpsError::EmptySparseArray),
             },
         };
 
         Ok(ans)
     }
+
+    async fn async_get_all(&self) -> OpsResult {
+        let ans = match (&self.serialization, self.selected_async_array()) {
+            (Serialization::Sparse, _) => self.get_all()?,
+            (Serialization::Zarr, Some(array)) => {
+                let shape = ArraySubset::new_with_shape(array.shape().to_vec());
+                OpsFormat::Zarr(
+                    array
+                        .async_retrieve_array_subset_elements::<u32>(&shape)
+                        .await
+                        .map_err(EngineError::from)?,
+                )
+            }
+            (Serialization::Zarr, None) => return Err(OpsError::EmptyArray),
+        };
+
+        Ok(ans)
+    }
+
+    /// Finds where the triples whose object is within a range of indices are,
+    /// the subject and predicate being optionally bound to a single index.
+    /// The range of objects narrows the rows or the columns that are read
+    /// unless the objects are stored in the cells, in which case their values
+    /// are the ones that are narrowed.
+    fn bounds(
+        &self,
+        subject: Option<&str>,
+        predicate: Option<&str>,
+        objects: Range<usize>,
+    ) -> Result<Bounds, OpsError> {
+        let subject = match subject {
+            Some(subject) => match self.dictionary.try_get_subject_idx(subject)? {
+                Some(index) => Some(index..index + 1),
+                None => return Err(OpsError::SubjectNotFound),
+            },
+            None => None,
+        };
+        let predicate = match predicate {
+            Some(predicate) => match self.dictionary.try_get_predicate_idx(predicate)? {
+                Some(index) => Some(index..index + 1),
+                None => return Err(OpsError::PredicateNotFound),
+            },
+            None => None,
+        };
+        let object = Some(objects);
+        let (first, second, third) = match self.reference_system {
+            ReferenceSystem::SPO => (subject, predicate, object),
+            ReferenceSystem::SOP => (subject, object, predicate),
+            ReferenceSystem::PSO => (predicate, subject, object),
+            ReferenceSystem::POS => (predicate, object, subject),
+            ReferenceSystem::OSP => (object, subject, predicate),
+            ReferenceSystem::OPS => (object, predicate, subject),
+        };
+
+        // The ranges are clamped to the shape of the array, while 0 is never
+        // a value, as it marks the cells in which no triple is stored
+        let clamp = |range: Option<Range<usize>>, size: usize| match range {
+            Some(range) => range.start.min(size)..range.end.min(size).max(range.start.min(size)),
+            None => 0..size,
+        };
+        Ok(Bounds {
+            rows: clamp(first, self.dimensionality.first_term_size),
+            values: second.map_or(1..usize::MAX, |values| values.start.max(1)..values.end),
+            columns: clamp(third, self.dimensionality.third_term_size),
+        })
+    }
+
+    pub(super) fn decode_triple(
+        &self,
+        first: usize,
+        second: usize,
+        third: usize,
+    ) -> Result<StringTriple, OpsError> {
+        let (subject, predicate, object) = self.reference_system.to_spo(first, second, third);
+
+        match (
+            self.dictionary.try_get_subject(subject)?,
+            self.dictionary.try_get_predicate(predicate)?,
+            self.dictionary.try_get_object(object)?,
+        ) {
+            (Some(subject), Some(predicate), Some(object)) => Ok((subject, predicate, object)),
+            _ => Err(OpsError::TripleNotDecoded),
+        }
+    }
+}
+
+/// The rows and columns of the array in which the triples matching a pattern
+/// are stored, next to the values that their cells may hold
+struct Bounds {
+    rows: Range<usize>,
+    values: Range<usize>,
+    columns: Range<usize>,
+}
+
+impl Bounds {
+    fn subset(&self) -> ArraySubset {
+        ArraySubset::new_with_ranges(&[
+            self.rows.start as u64..self.rows.end as u64,
+            self.columns.start as u64..self.columns.end as u64,
+        ])
+    }
+
+    /// The coordinates of the cells of the subset that was read from the array
+    fn cells(&self, values: Vec<u32>) -> Vec<(usize, usize, usize)> {
+        let columns = self.columns.len();
+        values
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, value)| self.values.contains(&(value as usize)))
+            .map(|(idx, value)| {
+                (
+                    self.rows.start + idx / columns,
+                    value as usize,
+                    self.columns.start + idx % columns,
+                )
+            })
+            .collect()
+    }
+
+    /// The coordinates of the entries of the sparse array within the bounds,
+    /// going only through the rows or columns in which it is compressed that
+    /// are within them
+    fn entries(&self, array: &ZarrArray) -> Vec<(usize, usize, usize)> {
+        let (outer, inner) = match array.is_csc() {
+            true => (self.columns.clone(), &self.rows),
+            false => (self.rows.clone(), &self.columns),
+        };
+        let start = outer.start;
+        array
+            .slice_outer(outer)
+            .outer_iterator()
+            .enumerate()
+            .flat_map(|(outer, entries)| {
+                entries
+                    .iter()
+                    .filter(|&(inner_idx, value)| {
+                        inner.contains(&inner_idx) && self.values.contains(value)
+                    })
+                    .map(|(inner, &value)| match array.is_csc() {
+                        true => (inner, value, start + outer),
+                        false => (start + outer, value, inner),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
 }
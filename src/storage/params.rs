@@ -1,11 +1,78 @@
+use rayon::ThreadPool;
+use rayon::ThreadPoolBuildError;
+use rayon::ThreadPoolBuilder;
 use std::num::NonZeroU64;
+use std::str::FromStr;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+use zarrs::storage::AsyncReadableStorageTraits;
+use zarrs::storage::ReadableWritableStorageTraits;
 
 use crate::dictionary::Dictionary;
+use crate::error::ParserError;
 use crate::io::Graph;
 
+/// Where the dataset is stored. `Memory` keeps the whole dataset in the memory
+/// of the process, so that it can be loaded by sharing the same store after it
+/// has been serialized. Apart from those, any store implementing the storage
+/// traits of `zarrs` can be used. `Memory` and `Store` can be written and read
+/// by the synchronous API, while `AsyncStore` can only be read by
+/// [`crate::storage::Storage::async_load`].
 pub enum Backend<'a> {
     FileSystem(&'a str),
     HTTP(&'a str),
+    Memory(Arc<MemoryStore>),
+    Store(Arc<dyn ReadableWritableStorageTraits>),
+    AsyncStore(Arc<dyn AsyncReadableStorageTraits>),
+}
+
+/// How syntax errors in the RDF dump are handled. `Strict` aborts at the first
+/// invalid statement, while `Lenient` skips them, reporting how many of them
+/// were found, together with the first ones, up to the provided number.
+#[derive(Clone, Copy)]
+pub enum ErrorStrategy {
+    Strict,
+    Lenient(usize),
+}
+
+/// The syntax in which an RDF dump is written. It can be parsed from the name
+/// of the format, or from any of the extensions of its files, regardless of
+/// their case; e.g. `turtle`, `ttl` or `N3`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RdfFormat {
+    NTriples,
+    Turtle,
+    RdfXml,
+    NQuads,
+    TriG,
+    JsonLd,
+    Hdt,
+}
+
+/// How the named graphs of N-Quads and TriG dumps are handled. `Merge` puts
+/// all the triples into the default graph, while `Keep` also stores the
+/// triples of each named graph in a partition of its own, next to the array
+/// containing all of them.
+#[derive(Clone, Copy)]
+pub enum GraphPolicy {
+    Merge,
+    Keep,
+}
+
+/// How the blank nodes of an RDF dump are labeled when serializing it. `Keep`
+/// stores them as they are labeled by the parser. `Scope` relabels them after
+/// the file they are read from, its name and a hash of its full path, or after
+/// the scope given by [`crate::storage::Storage::set_blank_node_scope`], so
+/// that the ones of different files are never merged. `Skolemize` replaces
+/// them with IRIs under the `/.well-known/genid/` path of the provided base,
+/// which is recorded in the metadata of the store. In both cases, they are
+/// numbered in the order in which they first appear; hence, their labels are
+/// stable.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlankNodePolicy {
+    Keep,
+    Scope,
+    Skolemize(String),
 }
 
 pub enum Serialization {
@@ -13,15 +80,50 @@ pub enum Serialization {
     Sparse,
 }
 
+#[derive(Clone, Copy)]
 pub enum ChunkingStrategy {
     Chunk,
     Sharding(u64),
     Best,
 }
 
+/// How the Dictionary is retrieved when loading a dataset. `Eager` downloads
+/// all the terms beforehand, while `Lazy` fetches only the parts of it that
+/// are needed to answer each query. By default, remote datasets are loaded
+/// lazily, while the ones in the file system are loaded eagerly.
+pub enum DictionaryStrategy {
+    Eager,
+    Lazy,
+}
+
+/// How the terms are split into the sections of the Dictionary. `ThreeSection`
+/// keeps the subjects, predicates and objects apart, while `FourSection`, as
+/// HDT does, moves the terms that are both subjects and objects into a shared
+/// section; hence, they are stored once, and have the same index in both roles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DictionaryFormat {
+    ThreeSection,
+    FourSection,
+}
+
+/// How the RDF dump is processed when serializing it. `InMemory` keeps all the
+/// terms and triples in memory, while `Streaming` keeps at most the provided
+/// number of them, spilling the rest into temporary files. Hence, the latter
+/// allows converting datasets that are larger than the available RAM. JSON-LD
+/// documents are the exception, as they are always read as a whole; moreover,
+/// `Streaming` reads the dump twice, so they are also converted twice.
+pub enum MemoryStrategy {
+    InMemory,
+    Streaming(usize),
+}
+
+/// The number of threads used for encoding and decoding the shards of the
+/// array. `Multi` takes the size of the thread pool; if it is 0, as many
+/// threads as logical CPUs are spawned. The resulting store is the same
+/// regardless of the number of threads.
 pub enum ThreadingStrategy {
     Single,
-    Multi,
+    Multi(usize),
 }
 
 #[derive(Clone)]
@@ -42,6 +144,29 @@ pub struct Dimensionality {
     pub(crate) third_term_size: usize,
 }
 
+impl GraphPolicy {
+    pub(crate) fn is_keep(&self) -> bool {
+        matches!(self, GraphPolicy::Keep)
+    }
+}
+
+impl FromStr for RdfFormat {
+    type Err = ParserError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "nt" | "ntriples" | "n-triples" => Ok(RdfFormat::NTriples),
+            "ttl" | "turtle" | "n3" => Ok(RdfFormat::Turtle),
+            "rdf" | "owl" | "xml" | "rdfxml" | "rdf/xml" => Ok(RdfFormat::RdfXml),
+            "nq" | "nquads" | "n-quads" => Ok(RdfFormat::NQuads),
+            "trig" => Ok(RdfFormat::TriG),
+            "jsonld" | "json-ld" | "json" => Ok(RdfFormat::JsonLd),
+            "hdt" => Ok(RdfFormat::Hdt),
+            _ => Err(ParserError::NotSupportedFormat(value.to_string())),
+        }
+    }
+}
+
 impl From<ChunkingStrategy> for NonZeroU64 {
     fn from(value: ChunkingStrategy) -> Self {
         match value {
@@ -52,6 +177,34 @@ impl From<ChunkingStrategy> for NonZeroU64 {
     }
 }
 
+impl TryFrom<ThreadingStrategy> for ThreadPool {
+    type Error = ThreadPoolBuildError;
+
+    fn try_from(value: ThreadingStrategy) -> Result<Self, Self::Error> {
+        ThreadPoolBuilder::new()
+            .num_threads(match value {
+                ThreadingStrategy::Single => 1,
+                ThreadingStrategy::Multi(num_threads) => num_threads,
+            })
+            .build()
+    }
+}
+
+impl ReferenceSystem {
+    /// Sorts the terms of a triple, which are given in the order of the
+    /// reference system, as subject, predicate and object
+    pub(crate) fn to_spo<T>(&self, first: T, second: T, third: T) -> (T, T, T) {
+        match self {
+            ReferenceSystem::SPO => (first, second, third),
+            ReferenceSystem::SOP => (first, third, second),
+            ReferenceSystem::PSO => (second, first, third),
+            ReferenceSystem::POS => (third, first, second),
+            ReferenceSystem::OSP => (second, third, first),
+            ReferenceSystem::OPS => (third, second, first),
+        }
+    }
+}
+
 impl AsRef<str> for ReferenceSystem {
     fn as_ref(&self) -> &str {
         match self {
@@ -81,11 +234,18 @@ impl From<&str> for ReferenceSystem {
 
 impl Dimensionality {
     pub(crate) fn new(dictionary: &Dictionary, graph: &Graph) -> Self {
-        Dimensionality {
-            graph_size: graph
+        Self::with_graph_size(
+            dictionary,
+            graph
                 .iter()
                 .map(|triples| triples.len())
                 .reduce(|acc, a| acc + a),
+        )
+    }
+
+    pub(crate) fn with_graph_size(dictionary: &Dictionary, graph_size: Option<usize>) -> Self {
+        Dimensionality {
+            graph_size,
             first_term_size: match dictionary.get_reference_system() {
                 ReferenceSystem::SPO | ReferenceSystem::SOP => dictionary.subjects_size(),
                 ReferenceSystem::POS | ReferenceSystem::PSO => dictionary.predicates_size(),
@@ -1,34 +1,136 @@
 use fcsd::Set;
-use serde_json::Value;
-use std::collections::HashSet;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use zarrs::array::codec::array_to_bytes::sharding::ShardingCodecBuilder;
+use zarrs::array::codec::GzipCodec;
 use zarrs::array::Array;
+use zarrs::array::ArrayBuilder;
+use zarrs::array::DataType;
+use zarrs::array::FillValue;
+use zarrs::array_subset::ArraySubset;
+use zarrs::storage::AsyncReadableStorageTraits;
+use zarrs::storage::ReadableStorageTraits;
+use zarrs::storage::ReadableWritableStorageTraits;
 
-pub fn rdf_to_value(terms: Set) -> Value {
-    terms
-        .iter()
-        .map(|(_, term)| std::str::from_utf8(&term).unwrap().to_string())
-        .collect::<Vec<_>>()
-        .into()
-}
-
-pub fn value_to_term(value: &Value) -> Vec<String> {
-    let mut terms = value
-        .as_array()
-        .unwrap()
-        .iter()
-        .map(|term| term.as_str().unwrap().to_string())
-        .collect::<Vec<String>>();
-    terms.sort();
-    terms
-}
-
-pub fn hash_to_set(terms: HashSet<String>) -> Vec<String> {
-    let mut vec = terms
-        .iter()
-        .map(|term| term.to_string())
-        .collect::<Vec<_>>();
-    vec.sort();
-    vec
+use crate::error::RemoteHDTError;
+use crate::storage::StorageResult;
+
+// The front-coded terms are stored as a 1D array of bytes, which is split into
+// shards that are composed of smaller chunks. This way, big dictionaries are
+// not stored in a single object, while a portion of them can be read without
+// fetching the whole shard
+pub(crate) const DICTIONARY_CHUNK_SIZE: u64 = 1 << 16;
+const DICTIONARY_SHARD_SIZE: u64 = 1 << 22;
+
+pub fn set_to_zarr<T: ReadableWritableStorageTraits + ?Sized + 'static>(
+    store: Arc<T>,
+    path: &str,
+    terms: &Set,
+) -> StorageResult<()> {
+    let mut bytes = Vec::with_capacity(terms.size_in_bytes());
+    if terms.serialize_into(&mut bytes).is_err() {
+        return Err(RemoteHDTError::DictionarySerialization);
+    }
+
+    let mut sharding_codec_builder =
+        ShardingCodecBuilder::new(vec![DICTIONARY_CHUNK_SIZE].try_into()?);
+    sharding_codec_builder.bytes_to_bytes_codecs(vec![Box::new(GzipCodec::new(5)?)]);
+
+    let arr = ArrayBuilder::new(
+        vec![bytes.len() as u64],
+        DataType::UInt8,
+        vec![NonZeroU64::new(DICTIONARY_SHARD_SIZE).unwrap()].into(),
+        FillValue::from(0u8),
+    )
+    .array_to_bytes_codec(Box::new(sharding_codec_builder.build()))
+    .build(store, path)?;
+
+    arr.store_metadata()?;
+    arr.store_array_subset_elements::<u8>(
+        &ArraySubset::new_with_shape(arr.shape().to_vec()),
+        bytes,
+    )?;
+
+    Ok(())
+}
+
+pub fn zarr_to_set(store: Arc<dyn ReadableStorageTraits>, path: &str) -> StorageResult<Set> {
+    let arr = Array::new(store, path)?;
+    let bytes = arr
+        .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(arr.shape().to_vec()))?;
+    match Set::deserialize_from(bytes.as_slice()) {
+        Ok(terms) => Ok(terms),
+        Err(_) => Err(RemoteHDTError::DictionaryDeserialization),
+    }
+}
+
+pub async fn async_zarr_to_set(
+    store: Arc<dyn AsyncReadableStorageTraits>,
+    path: &str,
+) -> StorageResult<Set> {
+    let arr = Array::async_new(store, path).await?;
+    let bytes = arr
+        .async_retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(
+            arr.shape().to_vec(),
+        ))
+        .await?;
+    match Set::deserialize_from(bytes.as_slice()) {
+        Ok(terms) => Ok(terms),
+        Err(_) => Err(RemoteHDTError::DictionaryDeserialization),
+    }
+}
+
+/// Stores the integer literals of a partition of the objects as they are, in
+/// ascending order, so that a range of values can be found by a binary search
+pub fn integers_to_zarr<T: ReadableWritableStorageTraits + ?Sized + 'static>(
+    store: Arc<T>,
+    path: &str,
+    values: &[i64],
+) -> StorageResult<()> {
+    let mut sharding_codec_builder =
+        ShardingCodecBuilder::new(vec![DICTIONARY_CHUNK_SIZE].try_into()?);
+    sharding_codec_builder.bytes_to_bytes_codecs(vec![Box::new(GzipCodec::new(5)?)]);
+
+    let arr = ArrayBuilder::new(
+        vec![values.len() as u64],
+        DataType::Int64,
+        vec![NonZeroU64::new(DICTIONARY_SHARD_SIZE).unwrap()].into(),
+        FillValue::from(0i64),
+    )
+    .array_to_bytes_codec(Box::new(sharding_codec_builder.build()))
+    .build(store, path)?;
+
+    arr.store_metadata()?;
+    arr.store_array_subset_elements::<i64>(
+        &ArraySubset::new_with_shape(arr.shape().to_vec()),
+        values.to_vec(),
+    )?;
+
+    Ok(())
+}
+
+pub fn zarr_to_integers(
+    store: Arc<dyn ReadableStorageTraits>,
+    path: &str,
+) -> StorageResult<Vec<i64>> {
+    let arr = Array::new(store, path)?;
+    Ok(
+        arr.retrieve_array_subset_elements::<i64>(&ArraySubset::new_with_shape(
+            arr.shape().to_vec(),
+        ))?,
+    )
+}
+
+pub async fn async_zarr_to_integers(
+    store: Arc<dyn AsyncReadableStorageTraits>,
+    path: &str,
+) -> StorageResult<Vec<i64>> {
+    let arr = Array::async_new(store, path).await?;
+    Ok(arr
+        .async_retrieve_array_subset_elements::<i64>(&ArraySubset::new_with_shape(
+            arr.shape().to_vec(),
+        ))
+        .await?)
 }
 
 pub fn rows_per_shard<T: ?Sized>(arr: &Array<T>) -> u64 {
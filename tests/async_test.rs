@@ -0,0 +1,113 @@
+use futures::future::try_join_all;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::AsyncOps;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::ops::OpsFormat;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+
+mod common;
+
+#[tokio::test]
+async fn async_get_subject_matrix_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let (_dir, path) = common::fs_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+
+    let expected = match storage
+        .load(Backend::FileSystem(&path), ThreadingStrategy::Single)?
+        .get_subject(common::Subject::Alan.into())?
+    {
+        OpsFormat::Zarr(expected) => expected,
+        _ => unreachable!(),
+    };
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let actual = match storage
+        .async_load(Backend::FileSystem(&path))
+        .await?
+        .async_get_subject(common::Subject::Alan.into())
+        .await?
+    {
+        OpsFormat::Zarr(actual) => actual,
+        _ => unreachable!(),
+    };
+
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_concurrent_queries_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let (_dir, path) =
+        common::fs_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    let subjects = [
+        common::Subject::Alan,
+        common::Subject::Warrington,
+        common::Subject::Wilmslow,
+        common::Subject::Bombe,
+    ]
+    .map(<&str>::from);
+
+    let storage = storage.load(Backend::FileSystem(&path), ThreadingStrategy::Single)?;
+    let expected = subjects
+        .iter()
+        .map(|&subject| storage.get_triples(Some(subject), None, None))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let storage = storage.async_load(Backend::FileSystem(&path)).await?;
+
+    // every query is polled concurrently, while sharing the same Storage
+    let actual = try_join_all(
+        subjects
+            .iter()
+            .map(|&subject| storage.async_get_triples(Some(subject), None, None)),
+    )
+    .await?;
+
+    assert_eq!(actual, expected);
+    assert!(
+        storage
+            .async_contains(
+                common::Subject::Wilmslow.into(),
+                common::Predicate::Country.into(),
+                common::Object::UK.into(),
+            )
+            .await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_load_tabular_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+
+    let (_dir, path) =
+        common::fs_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.async_load(Backend::FileSystem(&path)).await?;
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    );
+
+    Ok(())
+}
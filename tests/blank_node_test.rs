@@ -0,0 +1,218 @@
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::BlankNodePolicy;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::sync::Arc;
+use tempfile::TempDir;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+const PEOPLE: &str = "@prefix ex: <http://example.org/> .
+_:b0 ex:name \"Alan\" ;
+    ex:knows [ ex:name \"Joan\" ] .";
+
+const PLACES: &str = "@prefix ex: <http://example.org/> .
+_:b0 ex:name \"Wilmslow\" .";
+
+#[test]
+fn scoped_blank_nodes_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let (people, places) = (dir.path().join("people.ttl"), dir.path().join("places.ttl"));
+    fs::write(&people, PEOPLE)?;
+    fs::write(&places, PLACES)?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_blank_node_policy(BlankNodePolicy::Scope);
+    common::serialize_into_memory(&mut storage, places.to_str().unwrap())?;
+    let places = storage.get_dictionary();
+    let store = common::serialize_into_memory(&mut storage, people.to_str().unwrap())?;
+    let people = storage.get_dictionary();
+
+    // the same label is given a different blank node in each of the files,
+    // which are named after them, while the anonymous ones are numbered after
+    // the labeled ones
+    let (_, place) = places.iter_subjects().next().unwrap();
+    let (_, person) = people.iter_subjects().next().unwrap();
+    assert!(place.starts_with("_:places_ttl_") && place.ends_with("_1"));
+    assert!(person.starts_with("_:people_ttl_") && person.ends_with("_1"));
+    let anonymous = format!("{}2", person.trim_end_matches('1'));
+    assert!(people.get_object_idx(&anonymous).is_some());
+    assert_eq!(people.get_subject_idx("_:b0"), None);
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    assert_eq!(storage.get_blank_node_policy(), BlankNodePolicy::Scope);
+    assert!(storage.contains(&person, "<http://example.org/knows>", &anonymous)?);
+
+    Ok(())
+}
+
+#[test]
+fn same_name_scope_test() -> Result<(), Box<dyn Error>> {
+    let (first, second) = (TempDir::new()?, TempDir::new()?);
+    let (first, second) = (
+        first.path().join("data.ttl"),
+        second.path().join("data.ttl"),
+    );
+    fs::write(&first, PLACES)?;
+    fs::write(&second, PLACES)?;
+
+    // files of the same name are told apart by their full path, while the
+    // same file is always given the same labels
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_blank_node_policy(BlankNodePolicy::Scope);
+    let subjects = [&first, &second, &first]
+        .map(|path| {
+            common::serialize_into_memory(&mut storage, path.to_str().unwrap())?;
+            let (_, subject) = storage.get_dictionary().iter_subjects().next().unwrap();
+            Ok::<_, Box<dyn Error>>(subject)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_ne!(subjects[0], subjects[1]);
+    assert_eq!(subjects[0], subjects[2]);
+
+    Ok(())
+}
+
+#[test]
+fn explicit_scope_test() -> Result<(), Box<dyn Error>> {
+    let serialize_reader = |storage: &mut Storage<_>| {
+        storage.serialize_reader(
+            Backend::Store(Arc::new(MemoryStore::new())),
+            Cursor::new(PLACES.as_bytes()),
+            RdfFormat::Turtle,
+            ChunkingStrategy::Chunk,
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Single,
+        )?;
+        Ok::<_, RemoteHDTError>(storage.get_dictionary())
+    };
+
+    // readers cannot be told apart from each other unless they are given a
+    // scope of their own
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_blank_node_policy(BlankNodePolicy::Scope);
+    assert!(matches!(
+        serialize_reader(&mut storage),
+        Err(RemoteHDTError::MissingBlankNodeScope)
+    ));
+    assert!(matches!(
+        storage.serialize_triples(
+            Backend::Store(Arc::new(MemoryStore::new())),
+            [(
+                "_:b0".into(),
+                "<http://example.org/name>".into(),
+                "\"Alan\"".into()
+            )],
+            ChunkingStrategy::Chunk,
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Single,
+        ),
+        Err(RemoteHDTError::MissingBlankNodeScope)
+    ));
+
+    storage.set_blank_node_scope("places/2024");
+    let dictionary = serialize_reader(&mut storage)?;
+    assert_eq!(dictionary.get_subject_idx("_:places_2024_1"), Some(0));
+
+    // unless the blank nodes are kept as they are
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    assert_eq!(
+        serialize_reader(&mut storage)?.get_subject_idx("_:b0"),
+        Some(0)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stable_labels_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("people.ttl");
+    fs::write(&path, PEOPLE)?;
+
+    // both passes of the streaming parser give the same labels, which are the
+    // same as the ones given when the triples are kept in memory
+    let mut in_memory = Storage::new(TabularLayout, Serialization::Sparse);
+    in_memory.set_blank_node_policy(BlankNodePolicy::Scope);
+    let in_memory = common::serialize_into_memory(&mut in_memory, path.to_str().unwrap())?;
+    let mut streaming = Storage::new(TabularLayout, Serialization::Sparse);
+    streaming
+        .set_blank_node_policy(BlankNodePolicy::Scope)
+        .set_memory_strategy(MemoryStrategy::Streaming(1));
+    let streaming = common::serialize_into_memory(&mut streaming, path.to_str().unwrap())?;
+
+    let mut expected = Storage::new(TabularLayout, Serialization::Sparse);
+    expected.load(Backend::Store(in_memory), ThreadingStrategy::Single)?;
+    let mut actual = Storage::new(TabularLayout, Serialization::Sparse);
+    actual.load(Backend::Store(streaming), ThreadingStrategy::Single)?;
+    assert_eq!(
+        common::sorted_triples(&actual)?,
+        common::sorted_triples(&expected)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn skolemized_blank_nodes_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("people.ttl");
+    fs::write(&path, PEOPLE)?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage
+        .set_blank_node_policy(BlankNodePolicy::Skolemize("http://example.org/".into()))
+        .set_blank_node_scope("people");
+    let store = common::serialize_into_memory(&mut storage, path.to_str().unwrap())?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    assert_eq!(
+        storage.get_blank_node_policy(),
+        BlankNodePolicy::Skolemize("http://example.org/".into())
+    );
+    assert_eq!(storage.get_dictionary().iter_subjects().count(), 2);
+    assert!(storage
+        .get_dictionary()
+        .iter_subjects()
+        .all(|(_, subject)| subject.starts_with("<http://example.org/.well-known/genid/")));
+    assert!(storage.contains(
+        "<http://example.org/.well-known/genid/people_1>",
+        "<http://example.org/knows>",
+        "<http://example.org/.well-known/genid/people_2>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn kept_blank_nodes_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("places.ttl");
+    fs::write(&path, PLACES)?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let store = common::serialize_into_memory(&mut storage, path.to_str().unwrap())?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    assert_eq!(storage.get_blank_node_policy(), BlankNodePolicy::Keep);
+    assert_eq!(storage.get_dictionary().get_subject_idx("_:b0"), Some(0));
+
+    Ok(())
+}
@@ -1,41 +1,90 @@
 #![allow(dead_code)]
 
 use remote_hdt::dictionary::Dictionary;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::ops::TriplesResult;
 use remote_hdt::storage::params::Backend;
 use remote_hdt::storage::params::ChunkingStrategy;
 use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::ThreadingStrategy;
 use remote_hdt::storage::Storage;
 use sprs::CsMat;
 use sprs::TriMat;
-use std::fs::File;
+use std::sync::Arc;
+use tempfile::TempDir;
+use zarrs::storage::store::MemoryStore;
 
-pub const TABULAR_ZARR: &str = "tests/out/tabular.zarr";
-pub const MATRIX_ZARR: &str = "tests/out/matrix.zarr";
-pub const SHARDING_ZARR: &str = "tests/out/sharding.zarr";
-pub const LARGER_ZARR: &str = "tests/out/larger.zarr";
-pub const PSO_ZARR: &str = "tests/out/pso.zarr";
-pub const OPS_ZARR: &str = "tests/out/ops.zarr";
-pub const TABULAR_PSO_ZARR: &str = "tests/out/tabular_pso.zarr";
-pub const TABULAR_OPS_ZARR: &str = "tests/out/tabular_ops.zarr";
+/// Serializes the RDF dump into a fresh in-memory store, so that the tests
+/// using it do not depend on the state of the file system
+pub fn memory_setup<C>(
+    storage: &mut Storage<C>,
+    chunking_strategy: ChunkingStrategy,
+    reference_system: ReferenceSystem,
+) -> Arc<MemoryStore> {
+    serialize(
+        storage,
+        "resources/rdf.nt",
+        chunking_strategy,
+        reference_system,
+    )
+    .unwrap()
+}
 
-pub fn setup<C>(
+/// Serializes the dump at the provided path into a fresh in-memory store,
+/// using the default chunking strategy and reference system
+pub fn serialize_into_memory<C>(
+    storage: &mut Storage<C>,
     path: &str,
+) -> Result<Arc<MemoryStore>, RemoteHDTError> {
+    serialize(storage, path, ChunkingStrategy::Chunk, ReferenceSystem::SPO)
+}
+
+fn serialize<C>(
     storage: &mut Storage<C>,
+    path: &str,
     chunking_strategy: ChunkingStrategy,
     reference_system: ReferenceSystem,
-) {
-    if File::open(path).is_err() {
-        storage
-            .serialize(
-                Backend::FileSystem(path),
-                "resources/rdf.nt",
-                chunking_strategy,
-                reference_system,
-            )
-            .unwrap();
-    } else {
-        storage.load(Backend::FileSystem(path)).unwrap();
-    }
+) -> Result<Arc<MemoryStore>, RemoteHDTError> {
+    let store = Arc::new(MemoryStore::new());
+    storage.serialize(
+        Backend::Memory(store.clone()),
+        path,
+        chunking_strategy,
+        reference_system,
+        ThreadingStrategy::Single,
+    )?;
+    Ok(store)
+}
+
+/// Every triple of the dataset, sorted so that they can be compared
+/// regardless of the order in which they are stored
+pub fn sorted_triples<C>(storage: &Storage<C>) -> TriplesResult {
+    let mut triples = storage.get_triples(None, None, None)?;
+    triples.sort();
+    Ok(triples)
+}
+
+/// Serializes the dataset into a temporary directory of the file system, so
+/// that it can also be read by the asynchronous API. The directory is removed
+/// once the returned handle is dropped.
+pub fn fs_setup<C>(
+    storage: &mut Storage<C>,
+    chunking_strategy: ChunkingStrategy,
+    reference_system: ReferenceSystem,
+) -> (TempDir, String) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("root.zarr").to_str().unwrap().to_string();
+    storage
+        .serialize(
+            Backend::FileSystem(&path),
+            "resources/rdf.nt",
+            chunking_strategy,
+            reference_system,
+            ThreadingStrategy::Single,
+        )
+        .unwrap();
+    (dir, path)
 }
 
 pub enum Subject {
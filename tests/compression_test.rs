@@ -0,0 +1,94 @@
+use bzip2::write::BzEncoder;
+use flate2::write::GzEncoder;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::io::Write;
+use std::sync::Arc;
+use tempfile::TempDir;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+fn load(store: Arc<MemoryStore>) -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn gzip_path_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("rdf.nt.gz");
+    let mut encoder = GzEncoder::new(fs::File::create(&path)?, Default::default());
+    encoder.write_all(&fs::read("resources/rdf.nt")?)?;
+    encoder.finish()?;
+
+    let store = Arc::new(MemoryStore::new());
+    Storage::new(TabularLayout, Serialization::Sparse).serialize(
+        Backend::Store(store.clone()),
+        path.to_str().unwrap(),
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    load(store)
+}
+
+#[test]
+fn zstd_streaming_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("rdf.nt.zst");
+    fs::write(
+        &path,
+        zstd::encode_all(fs::File::open("resources/rdf.nt")?, 3)?,
+    )?;
+
+    let store = Arc::new(MemoryStore::new());
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_memory_strategy(MemoryStrategy::Streaming(2));
+    storage.serialize(
+        Backend::Store(store.clone()),
+        path.to_str().unwrap(),
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    load(store)
+}
+
+#[test]
+fn bzip2_reader_test() -> Result<(), Box<dyn Error>> {
+    // the compression of a reader is detected by its magic bytes
+    let mut encoder = BzEncoder::new(Vec::new(), Default::default());
+    encoder.write_all(&fs::read("resources/rdf.nt")?)?;
+
+    let store = Arc::new(MemoryStore::new());
+    Storage::new(TabularLayout, Serialization::Sparse).serialize_reader(
+        Backend::Store(store.clone()),
+        Cursor::new(encoder.finish()?),
+        RdfFormat::NTriples,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    load(store)
+}
@@ -0,0 +1,79 @@
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+
+mod common;
+
+#[test]
+fn reverse_lookup_spo_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
+        .unwrap();
+    let dictionary = storage.get_dictionary();
+
+    let subject = common::Subject::Bombe.get_idx(&dictionary);
+    let predicate = common::Predicate::Discoverer.get_idx(&dictionary);
+    let object = common::Object::Date.get_idx(&dictionary);
+
+    assert_eq!(
+        dictionary.get_subject_unchecked(subject),
+        <&str>::from(common::Subject::Bombe)
+    );
+    assert_eq!(
+        dictionary.get_predicate_unchecked(predicate),
+        <&str>::from(common::Predicate::Discoverer)
+    );
+    assert_eq!(
+        dictionary.get_object_unchecked(object),
+        <&str>::from(common::Object::Date)
+    );
+
+    // predicates are stored in the second position; hence, they are shifted
+    assert_eq!(dictionary.get_predicate(0), None);
+    assert!(dictionary
+        .get_predicate(dictionary.predicates_size())
+        .is_some());
+    assert_eq!(dictionary.get_subject(dictionary.subjects_size()), None);
+}
+
+#[test]
+fn reverse_lookup_pso_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
+        .unwrap();
+    let dictionary = storage.get_dictionary();
+
+    // subjects are stored in the second position; hence, they are shifted
+    assert_eq!(dictionary.get_subject(0), None);
+    assert!(dictionary.get_predicate(0).is_some());
+
+    let subjects = dictionary.iter_subjects().collect::<Vec<_>>();
+    assert_eq!(subjects.len(), dictionary.subjects_size());
+    subjects.iter().for_each(|(index, subject)| {
+        assert_eq!(dictionary.get_subject_idx_unchecked(subject), *index);
+    });
+
+    let decoded = dictionary
+        .decode_subjects(subjects.iter().map(|(index, _)| *index))
+        .collect::<Option<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        decoded,
+        subjects
+            .into_iter()
+            .map(|(_, subject)| subject)
+            .collect::<Vec<_>>()
+    );
+}
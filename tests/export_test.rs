@@ -0,0 +1,98 @@
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::io::Cursor;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+/// Serializes the exported dump into a fresh store, which is loaded back
+fn reserialize<C>(
+    storage: &mut Storage<C>,
+    dump: Vec<u8>,
+    format: RdfFormat,
+) -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        Cursor::new(dump),
+        format,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    Ok(())
+}
+
+#[test]
+fn ntriples_round_trip_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+
+    let mut original = Storage::new(MatrixLayout, Serialization::Sparse);
+    original.load(Backend::Store(store.clone()), ThreadingStrategy::Single)?;
+
+    // the triples are decoded shard by shard from the Zarr array
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let dump = storage.export(Vec::new(), RdfFormat::NTriples)?;
+    assert_eq!(String::from_utf8(dump.clone())?.lines().count(), 11);
+
+    let mut exported = Storage::new(MatrixLayout, Serialization::Sparse);
+    reserialize(&mut exported, dump, RdfFormat::NTriples)?;
+
+    assert_eq!(exported.get_sparse_array(), original.get_sparse_array());
+
+    Ok(())
+}
+
+#[test]
+fn turtle_export_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    // the triples sharing their subject are grouped together
+    let dump = storage.export(Vec::new(), RdfFormat::Turtle)?;
+    let turtle = String::from_utf8(dump.clone())?;
+    assert!(turtle.contains(";"));
+    assert!(turtle.contains("\"1912-06-23\"^^<http://www.w3.org/2001/XMLSchemadate>"));
+
+    let mut exported = Storage::new(MatrixLayout, Serialization::Zarr);
+    reserialize(&mut exported, dump, RdfFormat::Turtle)?;
+
+    assert_eq!(exported.get_dictionary().subjects_size(), 4);
+    assert!(exported.contains(
+        "<http://example.org/alan>",
+        "<http://example.org/dateOfBirth>",
+        "\"1912-06-23\"^^<http://www.w3.org/2001/XMLSchemadate>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn not_supported_export_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    assert!(matches!(
+        storage.export(Vec::new(), RdfFormat::NQuads),
+        Err(RemoteHDTError::NotSupportedExport(RdfFormat::NQuads))
+    ));
+}
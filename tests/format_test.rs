@@ -0,0 +1,94 @@
+use remote_hdt::error::ParserError;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+const TURTLE: &str = "@prefix ex: <http://example.org/> .
+ex:alan ex:instanceOf ex:Human ;
+    ex:placeOfBirth ex:warrington .";
+
+#[test]
+fn sniff_ntriples_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("dump");
+    fs::copy("resources/rdf.nt", &path)?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    let store = common::serialize_into_memory(&mut storage, path.to_str().unwrap())?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn explicit_format_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let uppercase = dir.path().join("DUMP.N3");
+    let unknown = dir.path().join("dump.txt");
+    fs::write(&uppercase, TURTLE)?;
+    fs::write(&unknown, TURTLE)?;
+
+    // both the extension, regardless of its case, and the content are used
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    common::serialize_into_memory(&mut storage, uppercase.to_str().unwrap())?;
+    assert_eq!(storage.get_dictionary().predicates_size(), 2);
+    common::serialize_into_memory(&mut storage, unknown.to_str().unwrap())?;
+    assert_eq!(storage.get_dictionary().predicates_size(), 2);
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_rdf_format(RdfFormat::Turtle);
+    let store = common::serialize_into_memory(&mut storage, unknown.to_str().unwrap())?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    assert!(storage.contains(
+        "<http://example.org/alan>",
+        "<http://example.org/placeOfBirth>",
+        "<http://example.org/warrington>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn not_supported_format_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("dump.txt");
+    fs::write(&path, "alan was born in warrington")?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    match common::serialize_into_memory(&mut storage, path.to_str().unwrap()) {
+        Err(RemoteHDTError::RdfParse(ParserError::NotSupportedFormat(format))) => {
+            assert_eq!(format, "txt")
+        }
+        _ => panic!("the format should not have been guessed"),
+    }
+
+    assert_eq!("TriG".parse::<RdfFormat>()?, RdfFormat::TriG);
+    assert_eq!("owl".parse::<RdfFormat>()?, RdfFormat::RdfXml);
+    assert!("csv"
+        .parse::<RdfFormat>()
+        .unwrap_err()
+        .to_string()
+        .contains("N-Quads (nq)"));
+
+    Ok(())
+}
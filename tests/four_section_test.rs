@@ -0,0 +1,256 @@
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::DictionaryFormat;
+use remote_hdt::storage::params::DictionaryStrategy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+fn sorted_terms(terms: impl Iterator<Item = (usize, String)>) -> Vec<String> {
+    let mut terms = terms.map(|(_, term)| term).collect::<Vec<_>>();
+    terms.sort();
+    terms
+}
+
+#[test]
+fn four_section_dictionary_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    storage.set_dictionary_format(DictionaryFormat::FourSection);
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let mut expected = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::memory_setup(
+        &mut expected,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+    expected.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    // alan, warrington and wilmslow are both subjects and objects
+    let dictionary = storage.get_dictionary();
+    assert_eq!(dictionary.shared_size(), 3);
+    assert_eq!(expected.get_dictionary().shared_size(), 0);
+    assert_eq!(dictionary.subjects_size(), 4);
+    assert_eq!(
+        dictionary.objects_size(),
+        expected.get_dictionary().objects_size()
+    );
+
+    // the position of every term in the views is its index, as it is SPO;
+    // the shared terms go first, so the order differs from the one of the
+    // three-section dictionary, though the terms are the same
+    let subjects = dictionary.subjects();
+    assert_eq!(subjects.len(), dictionary.subjects_size());
+    subjects.iter().for_each(|(position, subject)| {
+        assert_eq!(dictionary.get_subject_idx(&subject), Some(position));
+        assert_eq!(dictionary.get_subject(position), Some(subject.to_owned()));
+        assert_eq!(subjects.locate(&subject), Some(position));
+        assert_eq!(subjects.get(position), Some(subject));
+    });
+    let objects = dictionary.objects();
+    objects.iter().for_each(|(position, object)| {
+        assert_eq!(dictionary.get_object_idx(&object), Some(position));
+        assert_eq!(dictionary.get_object(position), Some(object.to_owned()));
+        assert_eq!(objects.locate(&object), Some(position));
+        assert_eq!(objects.get(position), Some(object));
+    });
+    assert_eq!(subjects.get(subjects.len()), None);
+    assert_eq!(
+        sorted_terms(subjects.iter()),
+        sorted_terms(expected.get_dictionary().subjects().iter())
+    );
+    assert_eq!(
+        sorted_terms(objects.iter()),
+        sorted_terms(expected.get_dictionary().objects().iter())
+    );
+
+    assert_eq!(
+        common::sorted_triples(&storage)?,
+        common::sorted_triples(&expected)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn shared_idx_test() -> Result<(), Box<dyn Error>> {
+    // the objects are the ones stored in the second position of the array
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_dictionary_format(DictionaryFormat::FourSection);
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SOP,
+    );
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let dictionary = storage.get_dictionary();
+
+    let alan = dictionary.get_subject_idx_unchecked(common::Subject::Alan.into());
+    assert_eq!(
+        dictionary.subject_to_object_idx(alan),
+        dictionary.get_object_idx(common::Subject::Alan.into())
+    );
+    assert_eq!(dictionary.object_to_subject_idx(alan + 1), Some(alan));
+    let bombe = dictionary.get_subject_idx_unchecked(common::Subject::Bombe.into());
+    assert_eq!(dictionary.subject_to_object_idx(bombe), None);
+    let uk = dictionary.get_object_idx_unchecked(common::Object::UK.into());
+    assert_eq!(dictionary.object_to_subject_idx(uk), None);
+
+    // the same joins are answered by decoding the terms otherwise
+    let mut expected = Storage::new(TabularLayout, Serialization::Sparse);
+    let store = common::memory_setup(
+        &mut expected,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SOP,
+    );
+    expected.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let expected = expected.get_dictionary();
+    let alan = expected.get_subject_idx_unchecked(common::Subject::Alan.into());
+    assert_eq!(
+        expected.subject_to_object_idx(alan),
+        expected.get_object_idx(common::Subject::Alan.into())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn lazy_four_section_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage
+        .set_dictionary_format(DictionaryFormat::FourSection)
+        .set_memory_strategy(MemoryStrategy::Streaming(2));
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::PSO,
+    );
+
+    let mut eager = Storage::new(MatrixLayout, Serialization::Zarr);
+    eager.load(Backend::Store(store.clone()), ThreadingStrategy::Single)?;
+    let mut lazy = Storage::new(MatrixLayout, Serialization::Zarr);
+    lazy.set_dictionary_strategy(DictionaryStrategy::Lazy)
+        .load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let (eager_dictionary, lazy_dictionary) = (eager.get_dictionary(), lazy.get_dictionary());
+    assert!(lazy_dictionary.is_lazy());
+    assert_eq!(lazy_dictionary.shared_size(), 3);
+    assert_eq!(
+        lazy_dictionary.iter_subjects().collect::<Vec<_>>(),
+        eager_dictionary.iter_subjects().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        lazy_dictionary.iter_objects().collect::<Vec<_>>(),
+        eager_dictionary.iter_objects().collect::<Vec<_>>()
+    );
+    eager_dictionary
+        .iter_subjects()
+        .for_each(|(index, subject)| {
+            assert_eq!(lazy_dictionary.get_subject_idx(&subject), Some(index));
+            assert_eq!(lazy_dictionary.get_subject(index), Some(subject));
+        });
+
+    assert_eq!(
+        common::sorted_triples(&lazy)?,
+        common::sorted_triples(&eager)?
+    );
+    assert!(lazy.contains(
+        common::Subject::Wilmslow.into(),
+        common::Predicate::Country.into(),
+        common::Object::UK.into(),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn streaming_four_section_test() -> Result<(), Box<dyn Error>> {
+    // the shared terms are found while merging the sorted runs, so both
+    // strategies must end up with the very same sections
+    let mut streaming = Storage::new(MatrixLayout, Serialization::Zarr);
+    streaming
+        .set_dictionary_format(DictionaryFormat::FourSection)
+        .set_memory_strategy(MemoryStrategy::Streaming(2));
+    let store = common::serialize_into_memory(&mut streaming, "resources/rdf.nt")?;
+    streaming.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let mut in_memory = Storage::new(MatrixLayout, Serialization::Zarr);
+    in_memory.set_dictionary_format(DictionaryFormat::FourSection);
+    let store = common::serialize_into_memory(&mut in_memory, "resources/rdf.nt")?;
+    in_memory.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let (streaming_dictionary, in_memory_dictionary) =
+        (streaming.get_dictionary(), in_memory.get_dictionary());
+    assert_eq!(
+        streaming_dictionary.shared_size(),
+        in_memory_dictionary.shared_size()
+    );
+    assert_eq!(
+        streaming_dictionary.iter_subjects().collect::<Vec<_>>(),
+        in_memory_dictionary.iter_subjects().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        streaming_dictionary.iter_objects().collect::<Vec<_>>(),
+        in_memory_dictionary.iter_objects().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        common::sorted_triples(&streaming)?,
+        common::sorted_triples(&in_memory)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn all_shared_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let triples = [("alan", "bombe"), ("bombe", "alan")].map(|(subject, object)| {
+        (
+            format!("<http://example.org/{}>", subject),
+            "<http://example.org/knows>".to_string(),
+            format!("<http://example.org/{}>", object),
+        )
+    });
+
+    // neither the subjects nor the objects sections are stored, as they are
+    // left empty
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage
+        .set_dictionary_format(DictionaryFormat::FourSection)
+        .serialize_triples(
+            Backend::Store(store.clone()),
+            triples,
+            ChunkingStrategy::Chunk,
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Single,
+        )?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let dictionary = storage.get_dictionary();
+    assert_eq!(dictionary.shared_size(), 2);
+    assert_eq!(dictionary.subjects_size(), 2);
+    assert_eq!(dictionary.objects_size(), 2);
+    assert!(storage.contains(
+        "<http://example.org/bombe>",
+        "<http://example.org/knows>",
+        "<http://example.org/alan>",
+    )?);
+
+    Ok(())
+}
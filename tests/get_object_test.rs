@@ -7,6 +7,7 @@ use remote_hdt::storage::params::Backend;
 use remote_hdt::storage::params::ChunkingStrategy;
 use remote_hdt::storage::params::ReferenceSystem;
 use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
 use remote_hdt::storage::Storage;
 use sprs::TriMat;
 use std::error::Error;
@@ -17,15 +18,14 @@ mod common;
 fn get_object_matrix_sharding_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
 
-    common::setup(
-        common::SHARDING_ZARR,
+    let store = common::memory_setup(
         &mut storage,
         ChunkingStrategy::Sharding(3),
         ReferenceSystem::SPO,
     );
 
     let actual = match storage
-        .load(Backend::FileSystem(common::SHARDING_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_object(common::Object::Date.into())?
     {
         OpsFormat::Zarr(actual) => actual,
@@ -53,15 +53,10 @@ fn get_object_matrix_sharding_test() -> Result<(), Box<dyn Error>> {
 fn get_object_tabular_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
 
-    common::setup(
-        common::TABULAR_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::SPO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::TABULAR_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_object(common::Object::Alan.into())?
     {
         OpsFormat::SparseArray(actual) => actual,
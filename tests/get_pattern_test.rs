@@ -0,0 +1,167 @@
+use common::set_expected_first_term_matrix;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::ops::OpsFormat;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use sprs::TriMat;
+use std::error::Error;
+
+mod common;
+
+#[test]
+fn get_subject_predicate_matrix_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let actual = match storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_subject_predicate(
+            common::Subject::Alan.into(),
+            common::Predicate::InstanceOf.into(),
+        )? {
+        OpsFormat::Zarr(actual) => actual,
+        _ => unreachable!(),
+    };
+
+    let mut expected = vec![0u32; storage.get_dictionary().objects_size()];
+    set_expected_first_term_matrix(
+        &mut expected,
+        common::Subject::Alan,
+        common::Predicate::InstanceOf,
+        common::Object::Human,
+        &storage.get_dictionary(),
+        ReferenceSystem::SPO,
+    );
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(String::from("Expected and actual results are not equals").into())
+    }
+}
+
+#[test]
+fn get_subject_object_matrix_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let actual = match storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_subject_object(
+            common::Subject::Alan.into(),
+            common::Object::Warrington.into(),
+        )? {
+        OpsFormat::Zarr(actual) => actual,
+        _ => unreachable!(),
+    };
+
+    let expected = vec![common::Predicate::PlaceOfBirth.get_idx(&storage.get_dictionary()) as u32];
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(String::from("Expected and actual results are not equals").into())
+    }
+}
+
+#[test]
+fn get_predicate_object_tabular_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let actual = match storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_predicate_object(common::Predicate::Country.into(), common::Object::UK.into())?
+    {
+        OpsFormat::SparseArray(actual) => actual,
+        _ => unreachable!(),
+    };
+
+    let mut expected = TriMat::new((
+        storage.get_dictionary().subjects_size(),
+        storage.get_dictionary().objects_size(),
+    ));
+    expected.add_triplet(
+        common::Subject::Warrington.get_idx(&storage.get_dictionary()),
+        common::Object::UK.get_idx(&storage.get_dictionary()),
+        common::Predicate::Country.get_idx(&storage.get_dictionary()),
+    );
+    expected.add_triplet(
+        common::Subject::Wilmslow.get_idx(&storage.get_dictionary()),
+        common::Object::UK.get_idx(&storage.get_dictionary()),
+        common::Predicate::Country.get_idx(&storage.get_dictionary()),
+    );
+
+    if actual == expected.to_csc() {
+        Ok(())
+    } else {
+        Err(String::from("Expected and actual results are not equals").into())
+    }
+}
+
+#[test]
+fn get_subject_object_pso_tabular_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    let actual = match storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_subject_object(common::Subject::Bombe.into(), common::Object::GCHQ.into())?
+    {
+        OpsFormat::SparseArray(actual) => actual,
+        _ => unreachable!(),
+    };
+
+    let mut expected = TriMat::new((
+        storage.get_dictionary().predicates_size(),
+        storage.get_dictionary().objects_size(),
+    ));
+    expected.add_triplet(
+        common::Predicate::Manufacturer.get_idx(&storage.get_dictionary()),
+        common::Object::GCHQ.get_idx(&storage.get_dictionary()),
+        common::Subject::Bombe.get_idx(&storage.get_dictionary()),
+    );
+
+    if actual == expected.to_csc() {
+        Ok(())
+    } else {
+        Err(String::from("Expected and actual results are not equals").into())
+    }
+}
+
+#[test]
+fn contains_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    let storage = storage.load(Backend::Store(store.clone()), ThreadingStrategy::Single)?;
+
+    assert!(storage.contains(
+        common::Subject::Wilmslow.into(),
+        common::Predicate::Country.into(),
+        common::Object::UK.into(),
+    )?);
+    assert!(!storage.contains(
+        common::Subject::Warrington.into(),
+        common::Predicate::InstanceOf.into(),
+        common::Object::Town.into(),
+    )?);
+    assert!(!storage.contains(
+        "<http://example.org/turing>",
+        common::Predicate::Country.into(),
+        common::Object::UK.into(),
+    )?);
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ use remote_hdt::storage::params::Backend;
 use remote_hdt::storage::params::ChunkingStrategy;
 use remote_hdt::storage::params::ReferenceSystem;
 use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
 use remote_hdt::storage::Storage;
 use sprs::TriMat;
 use std::error::Error;
@@ -17,15 +18,10 @@ mod common;
 fn get_subject_matrix_chunk_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
 
-    common::setup(
-        common::MATRIX_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::SPO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::MATRIX_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_subject(common::Subject::Alan.into())?
     {
         OpsFormat::Zarr(actual) => actual,
@@ -85,15 +81,14 @@ fn get_subject_matrix_chunk_test() -> Result<(), Box<dyn Error>> {
 fn get_subject_matrix_sharding_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
 
-    common::setup(
-        common::SHARDING_ZARR,
+    let store = common::memory_setup(
         &mut storage,
         ChunkingStrategy::Sharding(4),
         ReferenceSystem::SPO,
     );
 
     let actual = match storage
-        .load(Backend::FileSystem(common::SHARDING_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_subject(common::Subject::Wilmslow.into())?
     {
         OpsFormat::Zarr(actual) => actual,
@@ -101,8 +96,10 @@ fn get_subject_matrix_sharding_test() -> Result<(), Box<dyn Error>> {
     };
 
     let mut expected = vec![0u32; storage.get_dictionary().objects_size()];
-    expected[5] = common::Predicate::InstanceOf.get_idx(&storage.get_dictionary()) as u32;
-    expected[6] = common::Predicate::Country.get_idx(&storage.get_dictionary()) as u32;
+    expected[common::Object::Town.get_idx(&storage.get_dictionary())] =
+        common::Predicate::InstanceOf.get_idx(&storage.get_dictionary()) as u32;
+    expected[common::Object::UK.get_idx(&storage.get_dictionary())] =
+        common::Predicate::Country.get_idx(&storage.get_dictionary()) as u32;
 
     if actual == expected {
         Ok(())
@@ -115,15 +112,10 @@ fn get_subject_matrix_sharding_test() -> Result<(), Box<dyn Error>> {
 fn get_subject_tabular_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
 
-    common::setup(
-        common::TABULAR_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::SPO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::TABULAR_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_subject(common::Subject::Alan.into())?
     {
         OpsFormat::SparseArray(actual) => actual,
@@ -0,0 +1,178 @@
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::ops::StringTriple;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+fn triple(
+    subject: common::Subject,
+    predicate: common::Predicate,
+    object: common::Object,
+) -> StringTriple {
+    (
+        <&str>::from(subject).to_string(),
+        <&str>::from(predicate).to_string(),
+        <&str>::from(object).to_string(),
+    )
+}
+
+#[test]
+fn get_triples_subject_matrix_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let mut actual = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_triples(Some(common::Subject::Wilmslow.into()), None, None)?;
+    actual.sort();
+
+    let expected = vec![
+        triple(
+            common::Subject::Wilmslow,
+            common::Predicate::Country,
+            common::Object::UK,
+        ),
+        triple(
+            common::Subject::Wilmslow,
+            common::Predicate::InstanceOf,
+            common::Object::Town,
+        ),
+    ];
+
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn get_triples_second_term_pso_matrix_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    let mut actual = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_triples(Some(common::Subject::Bombe.into()), None, None)?;
+    actual.sort();
+
+    let expected = vec![
+        triple(
+            common::Subject::Bombe,
+            common::Predicate::Discoverer,
+            common::Object::Alan,
+        ),
+        triple(
+            common::Subject::Bombe,
+            common::Predicate::InstanceOf,
+            common::Object::Computer,
+        ),
+        triple(
+            common::Subject::Bombe,
+            common::Predicate::Manufacturer,
+            common::Object::GCHQ,
+        ),
+    ];
+
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn get_triples_predicate_object_ops_tabular_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::OPS);
+
+    let mut actual = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_triples(
+            None,
+            Some(common::Predicate::Country.into()),
+            Some(common::Object::UK.into()),
+        )?;
+    actual.sort();
+
+    let expected = vec![
+        triple(
+            common::Subject::Warrington,
+            common::Predicate::Country,
+            common::Object::UK,
+        ),
+        triple(
+            common::Subject::Wilmslow,
+            common::Predicate::Country,
+            common::Object::UK,
+        ),
+    ];
+
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn get_triples_unbound_tabular_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let storage = storage.load(Backend::Store(store.clone()), ThreadingStrategy::Single)?;
+
+    assert_eq!(storage.get_triples(None, None, None)?.len(), 11);
+    assert_eq!(
+        storage.get_triples(
+            Some(common::Subject::Alan.into()),
+            Some(common::Predicate::Employer.into()),
+            Some(common::Object::GCHQ.into()),
+        )?,
+        vec![triple(
+            common::Subject::Alan,
+            common::Predicate::Employer,
+            common::Object::GCHQ,
+        )]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn get_triples_padded_tabular_test() -> Result<(), Box<dyn Error>> {
+    // three triples in shards of two rows, so the last shard is padded, while
+    // the first subject is not related to the first object
+    let store = Arc::new(MemoryStore::new());
+    let triples = [("a", "z"), ("b", "y"), ("c", "x")].map(|(subject, object)| {
+        (
+            format!("<http://example.org/{}>", subject),
+            "<http://example.org/p>".to_string(),
+            format!("<http://example.org/{}>", object),
+        )
+    });
+    Storage::new(TabularLayout, Serialization::Sparse).serialize_triples(
+        Backend::Store(store.clone()),
+        triples,
+        ChunkingStrategy::Sharding(2),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    // the padding is not parsed into a triple
+    assert_eq!(storage.get_sparse_array().unwrap().nnz(), 3);
+    assert_eq!(storage.get_triples(None, None, None)?.len(), 3);
+
+    Ok(())
+}
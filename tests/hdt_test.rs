@@ -0,0 +1,257 @@
+use remote_hdt::error::ParserError;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::GraphPolicy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use tempfile::TempDir;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+#[test]
+fn hdt_import_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    // the format is guessed from the extension of the file
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.hdt",
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut imported = Storage::new(MatrixLayout, Serialization::Sparse);
+    imported.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let mut expected = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::memory_setup(
+        &mut expected,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+    expected.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let (imported_dictionary, expected_dictionary) =
+        (imported.get_dictionary(), expected.get_dictionary());
+    assert!(imported_dictionary
+        .subjects()
+        .iter()
+        .eq(expected_dictionary.subjects().iter()));
+    assert!(imported_dictionary
+        .objects()
+        .iter()
+        .eq(expected_dictionary.objects().iter()));
+    assert_eq!(imported.get_sparse_array(), expected.get_sparse_array());
+
+    Ok(())
+}
+
+/// Runs one of the command-line tools of hdt-cpp, which converts between HDT
+/// and N-Triples; the tests using them are ignored unless it is installed
+fn hdt_cpp(tool: &str, input: &Path, output: &Path) -> Result<(), Box<dyn Error>> {
+    let status = Command::new(tool)
+        .args(["-f", "ntriples"])
+        .arg(input)
+        .arg(output)
+        .status()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("{} exited with {}", tool, status).into()),
+    }
+}
+
+#[test]
+#[ignore = "requires rdf2hdt from hdt-cpp"]
+fn hdt_cpp_import_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let hdt = dir.path().join("rdf.hdt");
+    hdt_cpp("rdf2hdt", Path::new("resources/rdf.nt"), &hdt)?;
+
+    let mut imported = Storage::new(MatrixLayout, Serialization::Sparse);
+    import(&mut imported, fs::read(hdt)?)?;
+
+    let mut expected = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::memory_setup(&mut expected, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+    expected.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        common::sorted_triples(&imported)?,
+        common::sorted_triples(&expected)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn streaming_hdt_import_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Zarr);
+    storage.set_memory_strategy(MemoryStrategy::Streaming(2));
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.hdt",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::POS,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(storage.get_dictionary().predicates_size(), 8);
+    assert!(storage.contains(
+        "<http://example.org/alan>",
+        "<http://example.org/dateOfBirth>",
+        "\"1912-06-23\"^^<http://www.w3.org/2001/XMLSchemadate>",
+    )?);
+
+    Ok(())
+}
+
+/// Imports the HDT file from memory into a fresh store, which is loaded back
+fn import<C>(storage: &mut Storage<C>, hdt: Vec<u8>) -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        Cursor::new(hdt),
+        RdfFormat::Hdt,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    Ok(())
+}
+
+#[test]
+fn hdt_export_test() -> Result<(), Box<dyn Error>> {
+    // the triples are sorted in SPO order regardless of the reference system
+    let mut storage = Storage::new(TabularLayout, Serialization::Zarr);
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let hdt = storage.export(Vec::new(), RdfFormat::Hdt)?;
+    assert!(hdt.starts_with(b"$HDT"));
+
+    let mut exported = Storage::new(MatrixLayout, Serialization::Sparse);
+    import(&mut exported, hdt)?;
+
+    let mut expected = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::memory_setup(&mut expected, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+    expected.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(exported.get_sparse_array(), expected.get_sparse_array());
+
+    Ok(())
+}
+
+#[test]
+fn streaming_hdt_export_test() -> Result<(), Box<dyn Error>> {
+    // sorting the triples on disk yields the very same file
+    let mut storage = Storage::new(TabularLayout, Serialization::Zarr);
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::OSP);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let expected = storage.export(Vec::new(), RdfFormat::Hdt)?;
+    storage.set_memory_strategy(MemoryStrategy::Streaming(2));
+    assert_eq!(storage.export(Vec::new(), RdfFormat::Hdt)?, expected);
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "requires hdt2rdf from hdt-cpp"]
+fn hdt_cpp_export_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let dir = TempDir::new()?;
+    let (hdt, nt) = (dir.path().join("rdf.hdt"), dir.path().join("rdf.nt"));
+    fs::write(&hdt, storage.export(Vec::new(), RdfFormat::Hdt)?)?;
+    hdt_cpp("hdt2rdf", &hdt, &nt)?;
+
+    let mut exported = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = common::serialize_into_memory(&mut exported, nt.to_str().unwrap())?;
+    exported.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        common::sorted_triples(&exported)?,
+        common::sorted_triples(&storage)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn graph_hdt_export_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    storage.set_graph_policy(GraphPolicy::Keep);
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.nq",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    // only the terms of the selected graph are written
+    storage.select_graph(Some("<http://example.org/places>"))?;
+    let hdt = storage.export(Vec::new(), RdfFormat::Hdt)?;
+
+    let mut exported = Storage::new(MatrixLayout, Serialization::Sparse);
+    import(&mut exported, hdt)?;
+
+    assert_eq!(exported.get_dictionary().subjects_size(), 2);
+    assert_eq!(exported.get_dictionary().objects_size(), 1);
+    assert!(exported.contains(
+        "<http://example.org/wilmslow>",
+        "<http://example.org/country>",
+        "<http://example.org/uk>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn corrupted_hdt_test() -> Result<(), Box<dyn Error>> {
+    let mut bytes = fs::read("resources/rdf.hdt")?;
+    let last = bytes.len() - 5;
+    bytes[last] ^= 0xff;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let result = storage.serialize_reader(
+        Backend::Store(Arc::new(MemoryStore::new())),
+        Cursor::new(bytes),
+        RdfFormat::Hdt,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    );
+
+    assert!(matches!(
+        result,
+        Err(RemoteHDTError::RdfParse(ParserError::Hdt(_)))
+    ));
+
+    Ok(())
+}
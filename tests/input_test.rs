@@ -0,0 +1,110 @@
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+#[test]
+fn serialize_reader_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let reader = Cursor::new(fs::read("resources/rdf.nt")?);
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        reader,
+        RdfFormat::NTriples,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn serialize_turtle_reader_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let reader = Cursor::new(
+        "@prefix ex: <http://example.org/> .
+        ex:alan ex:instanceOf ex:Human ;
+            ex:placeOfBirth ex:warrington .
+        ex:warrington ex:country ex:uk ."
+            .as_bytes(),
+    );
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        reader,
+        RdfFormat::Turtle,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert!(storage.contains(
+        "<http://example.org/warrington>",
+        "<http://example.org/country>",
+        "<http://example.org/uk>",
+    )?);
+    assert_eq!(storage.get_dictionary().subjects_size(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn serialize_triples_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let triples = (0..4).map(|i| {
+        (
+            format!("<http://example.org/machine{}>", i),
+            "<http://example.org/instanceOf>".to_string(),
+            "<http://example.org/Computer>".to_string(),
+        )
+    });
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.serialize_triples(
+        Backend::Store(store.clone()),
+        triples,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::POS,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(storage.get_dictionary().subjects_size(), 4);
+    assert!(storage.contains(
+        "<http://example.org/machine3>",
+        "<http://example.org/instanceOf>",
+        "<http://example.org/Computer>",
+    )?);
+
+    Ok(())
+}
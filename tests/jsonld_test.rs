@@ -0,0 +1,294 @@
+use remote_hdt::error::ParserError;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::ops::StringTriple;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ErrorStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Cursor;
+use std::sync::Arc;
+use tempfile::TempDir;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+const CONTEXT_IRI: &str = "https://example.org/context.jsonld";
+
+#[test]
+fn local_context_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_jsonld_context(CONTEXT_IRI, "resources/context.jsonld");
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.jsonld",
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn remote_context_test() {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_error_strategy(ErrorStrategy::Strict);
+
+    // the context is never fetched over the network
+    let result = storage.serialize(
+        Backend::Store(Arc::new(MemoryStore::new())),
+        "resources/rdf.jsonld",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    );
+
+    match result {
+        Err(RemoteHDTError::RdfParse(ParserError::Syntax(err))) => {
+            assert!(err.message.contains(CONTEXT_IRI))
+        }
+        _ => panic!("the remote context should not have been resolved"),
+    }
+}
+
+#[test]
+fn inline_context_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let reader = Cursor::new(
+        r#"{
+            "@context": {
+                "@vocab": "http://example.org/",
+                "id": "@id",
+                "name": { "@language": "en" },
+                "colleagues": { "@container": "@list" }
+            },
+            "id": "http://example.org/alan",
+            "name": "Alan",
+            "colleagues": [{ "id": "http://example.org/max" }, { "name": "Joan" }]
+        }"#
+        .as_bytes(),
+    );
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        reader,
+        RdfFormat::JsonLd,
+        ChunkingStrategy::Sharding(2),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert!(storage.contains(
+        "<http://example.org/alan>",
+        "<http://example.org/name>",
+        "\"Alan\"@en",
+    )?);
+    // the name, the head of the list, and two cells holding the colleagues,
+    // one of which is described by its own name
+    assert_eq!(storage.get_triples(None, None, None)?.len(), 7);
+
+    Ok(())
+}
+
+/// Parses a dump of the provided format, returning all of its triples
+fn triples(
+    reader: impl BufRead + 'static,
+    format: RdfFormat,
+) -> Result<Vec<StringTriple>, Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_error_strategy(ErrorStrategy::Strict);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        reader,
+        format,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+    let triples = storage.get_triples(None, None, None)?;
+
+    // The blank nodes are relabeled in the order in which they appear once the
+    // triples are sorted regardless of them, so that both sets can be compared
+    let masked = |term: &str| match term.starts_with("_:") {
+        true => "_:".to_string(),
+        false => term.to_string(),
+    };
+    let mut triples = triples
+        .into_iter()
+        .map(|(s, p, o)| ((masked(&s), p.to_owned(), masked(&o)), (s, p, o)))
+        .collect::<Vec<_>>();
+    triples.sort();
+    let mut labels = HashMap::new();
+    let mut relabel = |term: String| match term.starts_with("_:") {
+        true => {
+            let next = labels.len();
+            format!("_:c{}", labels.entry(term).or_insert(next))
+        }
+        false => term,
+    };
+    let mut triples = triples
+        .into_iter()
+        .map(|(_, (s, p, o))| (relabel(s), p, relabel(o)))
+        .collect::<Vec<_>>();
+    triples.sort();
+    Ok(triples)
+}
+
+#[test]
+fn to_rdf_test() -> Result<(), Box<dyn Error>> {
+    // every document in the style of the toRdf tests of the W3C suite is
+    // converted into the statements of its expected N-Quads file
+    let mut cases = fs::read_dir("resources/jsonld")?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    cases.retain(|path| path.to_string_lossy().ends_with("-in.jsonld"));
+    assert!(!cases.is_empty());
+
+    for input in cases {
+        let name = input.to_string_lossy().replace("-in.jsonld", "");
+        let expected = BufReader::new(File::open(format!("{}-out.nq", name))?);
+        let input = BufReader::new(File::open(&input)?);
+        assert_eq!(
+            triples(input, RdfFormat::JsonLd)?,
+            triples(expected, RdfFormat::NQuads)?,
+            "{}",
+            name
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unsupported_keywords_test() {
+    // the documents whose meaning depends on the features that are not
+    // supported are rejected, instead of being converted into other statements
+    for (document, message) in [
+        (
+            r#"{ "@context": { "@protected": true, "name": "http://example.org/name" },
+                 "@id": "http://example.org/alan", "name": "Alan" }"#,
+            "@protected",
+        ),
+        (
+            r#"{ "@context": { "@propagate": false, "name": "http://example.org/name" },
+                 "@id": "http://example.org/alan", "name": "Alan" }"#,
+            "@propagate",
+        ),
+        (
+            r#"{ "@context": { "knows": { "@id": "http://example.org/knows",
+                 "@context": { "name": "http://example.org/nickname" } } },
+                 "@id": "http://example.org/alan", "knows": { "name": "Joan" } }"#,
+            "@context",
+        ),
+        (
+            r#"{ "@context": { "ex": { "@id": "http://example.org/", "@prefix": true } },
+                 "@id": "http://example.org/alan", "ex:name": "Alan" }"#,
+            "@prefix",
+        ),
+        (
+            r#"{ "@id": "alan", "http://example.org/name": "Alan" }"#,
+            "alan",
+        ),
+        (
+            r#"{ "@context": { "knows": { "@id": "http://example.org/knows", "@container": "@id" } },
+                 "@id": "http://example.org/alan",
+                 "knows": { "http://example.org/joan": { "http://example.org/name": "Joan" } } }"#,
+            "container @id",
+        ),
+        (
+            r#"{ "@context": { "places": { "@id": "http://example.org/places", "@container": "@type" } },
+                 "@id": "http://example.org/alan",
+                 "places": { "http://example.org/Town": { "@id": "http://example.org/wilmslow" } } }"#,
+            "container @type",
+        ),
+        (
+            r#"{ "@context": { "graphs": { "@id": "http://example.org/graphs",
+                 "@container": ["@graph", "@index"] } },
+                 "@id": "http://example.org/alan",
+                 "graphs": { "people": { "@id": "http://example.org/joan" } } }"#,
+            "container @graph",
+        ),
+    ] {
+        match triples(Cursor::new(document.as_bytes()), RdfFormat::JsonLd) {
+            Err(err) => assert!(err.to_string().contains(message), "{}", err),
+            Ok(_) => panic!("the document should have been rejected: {}", document),
+        }
+    }
+}
+
+#[test]
+fn relative_context_test() -> Result<(), Box<dyn Error>> {
+    let dir = TempDir::new()?;
+    let dump = dir.path().join("dump").join("rdf.jsonld");
+    fs::create_dir(dump.parent().unwrap())?;
+    fs::copy(
+        "resources/context.jsonld",
+        dir.path().join("context.jsonld"),
+    )?;
+    fs::copy(
+        "resources/context.jsonld",
+        dir.path().join("dump/context.jsonld"),
+    )?;
+    let serialize = |context: &str| {
+        let document = fs::read_to_string("resources/rdf.jsonld")?.replace(CONTEXT_IRI, context);
+        fs::write(&dump, document)?;
+        let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+        storage.set_error_strategy(ErrorStrategy::Strict);
+        storage.serialize(
+            Backend::Store(Arc::new(MemoryStore::new())),
+            dump.to_str().unwrap(),
+            ChunkingStrategy::Chunk,
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Single,
+        )?;
+        Ok::<_, Box<dyn Error>>(())
+    };
+
+    // the relative references are resolved against the directory of the dump
+    serialize("context.jsonld")?;
+    serialize("./context.jsonld")?;
+    // but no other local file is read unless its path is provided
+    let outside = dir.path().join("context.jsonld");
+    for context in [
+        "../context.jsonld".to_string(),
+        outside.to_str().unwrap().to_string(),
+        format!("file://{}", outside.to_str().unwrap()),
+    ] {
+        match serialize(&context) {
+            Err(err) => assert!(err.to_string().contains(&context), "{}", err),
+            Ok(_) => panic!("the context {} should not have been read", context),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use remote_hdt::error::OpsError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::DictionaryStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use zarrs::array::MaybeBytes;
+use zarrs::byte_range::ByteRange;
+use zarrs::storage::store::MemoryStore;
+use zarrs::storage::store_lock::StoreKeyMutex;
+use zarrs::storage::ReadableStorageTraits;
+use zarrs::storage::ReadableWritableStorageTraits;
+use zarrs::storage::StorageError;
+use zarrs::storage::StoreKey;
+use zarrs::storage::StoreKeyRange;
+use zarrs::storage::StoreKeyStartValue;
+use zarrs::storage::StorePrefix;
+use zarrs::storage::WritableStorageTraits;
+
+mod common;
+
+/// A store that keeps track of the byte ranges read from every key, where
+/// retrieving a whole value is recorded as a range from its start. Reads
+/// fail while it is offline, as an unreachable remote store would
+#[derive(Default)]
+struct CountingStore {
+    store: MemoryStore,
+    reads: Mutex<Vec<(String, ByteRange)>>,
+    offline: AtomicBool,
+}
+
+impl CountingStore {
+    /// The number of chunks read from an array since the last call. The
+    /// index of the shard is read from its end, so it is not counted
+    fn chunks_read(&self, array: &str) -> usize {
+        let prefix = format!("{}/c/", array);
+        self.reads
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter(|(key, range)| {
+                key.starts_with(&prefix) && matches!(range, ByteRange::FromStart(..))
+            })
+            .count()
+    }
+
+    fn read(&self, key: &StoreKey, range: ByteRange) -> Result<(), StorageError> {
+        if self.offline.load(Ordering::Relaxed) {
+            return Err(StorageError::Other("The store is offline".to_string()));
+        }
+        self.reads.lock().unwrap().push((key.to_string(), range));
+        Ok(())
+    }
+}
+
+impl ReadableStorageTraits for CountingStore {
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.read(key, ByteRange::FromStart(0, None))?;
+        self.store.get(key)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Vec<u8>>>, StorageError> {
+        for range in byte_ranges {
+            self.read(key, *range)?;
+        }
+        self.store.get_partial_values_key(key, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.get_partial_values_batched_by_key(key_ranges)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.store.size_prefix(prefix)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.store.size_key(key)
+    }
+}
+
+impl WritableStorageTraits for CountingStore {
+    fn set(&self, key: &StoreKey, value: &[u8]) -> Result<(), StorageError> {
+        self.store.set(key, value)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_start_values: &[StoreKeyStartValue],
+    ) -> Result<(), StorageError> {
+        self.store.set_partial_values(key_start_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.store.erase(key)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.store.erase_prefix(prefix)
+    }
+}
+
+impl ReadableWritableStorageTraits for CountingStore {
+    fn mutex(&self, key: &StoreKey) -> Result<StoreKeyMutex, StorageError> {
+        self.store.mutex(key)
+    }
+}
+
+#[test]
+fn lazy_dictionary_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    let eager = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_dictionary();
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_dictionary_strategy(DictionaryStrategy::Lazy);
+    let lazy = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_dictionary();
+
+    assert!(!eager.is_lazy());
+    assert!(lazy.is_lazy());
+    assert_eq!(lazy.subjects_size(), eager.subjects_size());
+    assert_eq!(lazy.predicates_size(), eager.predicates_size());
+    assert_eq!(lazy.objects_size(), eager.objects_size());
+
+    assert_eq!(
+        lazy.iter_subjects().collect::<Vec<_>>(),
+        eager.iter_subjects().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        lazy.iter_predicates().collect::<Vec<_>>(),
+        eager.iter_predicates().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        lazy.iter_objects().collect::<Vec<_>>(),
+        eager.iter_objects().collect::<Vec<_>>()
+    );
+
+    eager.iter_objects().for_each(|(index, object)| {
+        assert_eq!(lazy.get_object_idx(&object), Some(index));
+        assert_eq!(lazy.get_object(index), Some(object));
+    });
+    assert_eq!(lazy.get_subject_idx("<http://example.org/turing>"), None);
+    assert_eq!(lazy.get_object(lazy.objects_size()), None);
+
+    Ok(())
+}
+
+#[test]
+fn lazy_dictionary_ops_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let expected = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_triples(None, Some(common::Predicate::InstanceOf.into()), None)?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_dictionary_strategy(DictionaryStrategy::Lazy);
+    let actual = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_triples(None, Some(common::Predicate::InstanceOf.into()), None)?;
+
+    assert_eq!(actual, expected);
+    assert!(storage.contains(
+        common::Subject::Wilmslow.into(),
+        common::Predicate::Country.into(),
+        common::Object::UK.into(),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn lazy_dictionary_chunks_test() -> Result<(), Box<dyn Error>> {
+    // every subject is long enough for the front-coded set to span several
+    // chunks of the array, as they barely share any prefix
+    let subject = |index: usize| format!("<http://example.org/{:05}/{}>", index, "x".repeat(100));
+    let triples = (0..3000).map(|index| {
+        (
+            subject(index),
+            "<http://example.org/knows>".to_string(),
+            "<http://example.org/alan>".to_string(),
+        )
+    });
+    let store = Arc::new(CountingStore::default());
+    Storage::new(MatrixLayout, Serialization::Zarr).serialize_triples(
+        Backend::Store(store.clone()),
+        triples,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    // the subjects take five chunks, out of which only the first and the
+    // last ones are read when loading, as they hold the header and trailer
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_dictionary_strategy(DictionaryStrategy::Lazy);
+    store.chunks_read("");
+    let dictionary = storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
+        .get_dictionary();
+    assert_eq!(store.chunks_read("group/subjects"), 2);
+
+    // the binary search only fetches the chunks of the buckets it visits,
+    // which are cached afterwards
+    assert_eq!(dictionary.get_subject_idx(&subject(1500)), Some(1500));
+    let chunks = store.chunks_read("group/subjects");
+    assert!(chunks > 0 && chunks < 3);
+    assert_eq!(dictionary.get_subject_idx(&subject(1500)), Some(1500));
+    assert_eq!(dictionary.get_subject(0), Some(subject(0)));
+    assert_eq!(dictionary.get_subject(2999), Some(subject(2999)));
+    assert_eq!(store.chunks_read("group/subjects"), 0);
+
+    // the terms that cannot be fetched are not reported as missing ones
+    store.offline.store(true, Ordering::Relaxed);
+    assert!(matches!(
+        storage.get_subject(&subject(500)),
+        Err(OpsError::Dictionary(_))
+    ));
+    assert!(dictionary.try_get_subject_idx(&subject(500)).is_err());
+
+    Ok(())
+}
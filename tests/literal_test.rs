@@ -0,0 +1,366 @@
+use remote_hdt::dictionary::partition::TermKind;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::DictionaryStrategy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use std::sync::Arc;
+use tempfile::TempDir;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+const INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const INT: &str = "http://www.w3.org/2001/XMLSchema#int";
+const BORN: &str = "<http://example.org/born>";
+const NAME: &str = "<http://example.org/name>";
+
+fn person(name: &str) -> String {
+    format!("<http://example.org/{}>", name)
+}
+
+fn store(reference_system: ReferenceSystem) -> Result<Arc<MemoryStore>, Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let triples = [
+        ("alan", BORN, format!("\"1912\"^^<{}>", INTEGER)),
+        ("ada", BORN, format!("\"1815\"^^<{}>", INTEGER)),
+        ("grace", BORN, format!("\"1906\"^^<{}>", INTEGER)),
+        ("claude", BORN, format!("\"-1\"^^<{}>", INTEGER)),
+        ("alan", NAME, "\"Alan Turing\"@en".to_string()),
+        ("ada", NAME, "\"Ada \\\"Lovelace\\\"\"".to_string()),
+        ("grace", NAME, "_:grace".to_string()),
+        // not written in its canonical form, so it is kept as a string
+        ("claude", NAME, format!("\"+7\"^^<{}>", INT)),
+        ("alan", "<http://example.org/knows>", person("ada")),
+    ]
+    .map(|(subject, predicate, object)| (person(subject), predicate.to_string(), object));
+
+    Storage::new(MatrixLayout, Serialization::Zarr).serialize_triples(
+        Backend::Store(store.clone()),
+        triples,
+        ChunkingStrategy::Chunk,
+        reference_system,
+        ThreadingStrategy::Single,
+    )?;
+
+    Ok(store)
+}
+
+#[test]
+fn object_partitions_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(
+        Backend::Store(store(ReferenceSystem::SPO)?),
+        ThreadingStrategy::Single,
+    )?;
+    let dictionary = storage.get_dictionary();
+
+    let kinds = dictionary
+        .object_partitions()
+        .into_iter()
+        .map(|(kind, range)| (kind, range.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            (TermKind::Iri, 1),
+            (TermKind::BlankNode, 1),
+            (TermKind::Literal, 1),
+            (TermKind::LanguageLiteral("en".to_string()), 1),
+            (TermKind::TypedLiteral(INT.to_string()), 1),
+            (TermKind::TypedLiteral(INTEGER.to_string()), 4),
+        ]
+    );
+    assert_eq!(dictionary.literals(), 2..9);
+
+    // every object is found where it is listed
+    dictionary.iter_objects().for_each(|(index, object)| {
+        assert_eq!(dictionary.get_object_idx(&object), Some(index));
+        assert_eq!(dictionary.get_object(index), Some(object.to_owned()));
+        assert_eq!(
+            dictionary.get_object_kind(index),
+            Some(TermKind::of(&object))
+        );
+    });
+
+    Ok(())
+}
+
+#[test]
+fn typed_accessors_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(
+        Backend::Store(store(ReferenceSystem::SPO)?),
+        ThreadingStrategy::Single,
+    )?;
+    let dictionary = storage.get_dictionary();
+
+    let year = dictionary.get_object_idx_unchecked(&format!("\"1912\"^^<{}>", INTEGER));
+    assert_eq!(dictionary.get_integer(year), Some(1912));
+    assert_eq!(
+        dictionary.get_literal(year),
+        Some((
+            "1912".to_string(),
+            TermKind::TypedLiteral(INTEGER.to_string())
+        ))
+    );
+    let int = dictionary.get_object_idx_unchecked(&format!("\"+7\"^^<{}>", INT));
+    assert_eq!(dictionary.get_integer(int), Some(7));
+
+    let name = dictionary.get_object_idx_unchecked("\"Ada \\\"Lovelace\\\"\"");
+    assert_eq!(
+        dictionary.get_literal(name),
+        Some(("Ada \"Lovelace\"".to_string(), TermKind::Literal))
+    );
+    assert_eq!(dictionary.get_integer(name), None);
+    let ada = dictionary.get_object_idx_unchecked(&person("ada"));
+    assert_eq!(dictionary.get_literal(ada), None);
+    assert_eq!(dictionary.get_object_kind(ada), Some(TermKind::Iri));
+
+    Ok(())
+}
+
+#[test]
+fn integer_range_test() -> Result<(), Box<dyn Error>> {
+    for serialization in [Serialization::Zarr, Serialization::Sparse] {
+        let mut storage = Storage::new(MatrixLayout, serialization);
+        storage.load(
+            Backend::Store(store(ReferenceSystem::POS)?),
+            ThreadingStrategy::Single,
+        )?;
+        let dictionary = storage.get_dictionary();
+
+        // the literals that are not canonical cannot be queried by value
+        assert_eq!(dictionary.get_integer_range(INT, ..), None);
+        let range = dictionary.get_integer_range(INTEGER, 1900..=1912).unwrap();
+        assert_eq!(range.len(), 2);
+        assert_eq!(
+            dictionary.get_integer_range(INTEGER, 2000..).unwrap().len(),
+            0
+        );
+
+        let mut triples = storage.get_triples_in_range(None, Some(BORN), range)?;
+        triples.sort();
+        assert_eq!(
+            triples,
+            [("alan", "1912"), ("grace", "1906")].map(|(subject, year)| (
+                person(subject),
+                BORN.to_string(),
+                format!("\"{}\"^^<{}>", year, INTEGER)
+            ))
+        );
+
+        // the literals can be retrieved apart from the rest of the objects
+        let literals = dictionary.literals();
+        let triples = storage.get_triples_in_range(Some(&person("alan")), None, literals)?;
+        assert_eq!(triples.len(), 2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn range_reference_systems_test() -> Result<(), Box<dyn Error>> {
+    // the range narrows the rows, the columns or the values of the array,
+    // depending on where the objects are stored
+    for reference_system in [
+        ReferenceSystem::SPO,
+        ReferenceSystem::SOP,
+        ReferenceSystem::PSO,
+        ReferenceSystem::POS,
+        ReferenceSystem::OSP,
+        ReferenceSystem::OPS,
+    ] {
+        let store = store(reference_system)?;
+        for serialization in [Serialization::Zarr, Serialization::Sparse] {
+            let mut storage = Storage::new(MatrixLayout, serialization);
+            storage.load(Backend::Store(store.clone()), ThreadingStrategy::Single)?;
+            let dictionary = storage.get_dictionary();
+            let range = dictionary.get_integer_range(INTEGER, ..1900).unwrap();
+
+            for (subject, predicate) in [
+                (None, None),
+                (Some(person("ada")), None),
+                (None, Some(BORN)),
+                (Some(person("ada")), Some(BORN)),
+                (Some(person("alan")), Some(BORN)),
+            ] {
+                let subject = subject.as_deref();
+                let mut expected = storage
+                    .get_triples(subject, predicate, None)?
+                    .into_iter()
+                    .filter(|(_, _, object)| {
+                        range.contains(&dictionary.get_object_idx_unchecked(object))
+                    })
+                    .collect::<Vec<_>>();
+                expected.sort();
+                let mut actual = storage.get_triples_in_range(subject, predicate, range.clone())?;
+                actual.sort();
+                assert_eq!(actual, expected);
+            }
+
+            let mut triples = storage.get_triples_in_range(None, None, range)?;
+            triples.sort();
+            assert_eq!(
+                triples,
+                [("ada", "1815"), ("claude", "-1")].map(|(subject, year)| (
+                    person(subject),
+                    BORN.to_string(),
+                    format!("\"{}\"^^<{}>", year, INTEGER)
+                ))
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn lazy_partitions_test() -> Result<(), Box<dyn Error>> {
+    let store = store(ReferenceSystem::OSP)?;
+    let mut eager = Storage::new(MatrixLayout, Serialization::Zarr);
+    eager.load(Backend::Store(store.clone()), ThreadingStrategy::Single)?;
+    let mut lazy = Storage::new(MatrixLayout, Serialization::Zarr);
+    lazy.set_dictionary_strategy(DictionaryStrategy::Lazy)
+        .load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let (eager, lazy) = (eager.get_dictionary(), lazy.get_dictionary());
+    assert!(lazy.is_lazy());
+    assert_eq!(
+        lazy.iter_objects().collect::<Vec<_>>(),
+        eager.iter_objects().collect::<Vec<_>>()
+    );
+    assert_eq!(lazy.object_partitions(), eager.object_partitions());
+    eager.iter_objects().for_each(|(index, object)| {
+        assert_eq!(lazy.get_object_idx(&object), Some(index));
+    });
+
+    Ok(())
+}
+
+#[test]
+fn streaming_partitions_test() -> Result<(), Box<dyn Error>> {
+    // the integers are not sorted as their strings, while the last literal of
+    // the int datatype turns the ones before it back into strings
+    let objects = [
+        format!("\"10\"^^<{}>", INTEGER),
+        format!("\"9\"^^<{}>", INTEGER),
+        format!("\"-3\"^^<{}>", INTEGER),
+        format!("\"1\"^^<{}>", INT),
+        format!("\"5\"^^<{}>", INT),
+        format!("\"9\"^^<{}>", INT),
+        format!("\"9.0\"^^<{}>", INT),
+        "\"Alan Turing\"@en".to_string(),
+        "_:grace".to_string(),
+        person("ada"),
+    ];
+    let dir = TempDir::new()?;
+    let path = dir.path().join("rdf.nt");
+    fs::write(
+        &path,
+        objects
+            .iter()
+            .enumerate()
+            .map(|(i, object)| format!("{} {} {} .\n", person(&i.to_string()), BORN, object))
+            .collect::<String>(),
+    )?;
+
+    let mut in_memory = Storage::new(MatrixLayout, Serialization::Zarr);
+    let store = common::serialize_into_memory(&mut in_memory, path.to_str().unwrap())?;
+    in_memory.load(Backend::Memory(store), ThreadingStrategy::Single)?;
+    let mut streaming = Storage::new(MatrixLayout, Serialization::Zarr);
+    streaming.set_memory_strategy(MemoryStrategy::Streaming(2));
+    let store = common::serialize_into_memory(&mut streaming, path.to_str().unwrap())?;
+    streaming.load(Backend::Memory(store), ThreadingStrategy::Single)?;
+
+    let (expected, actual) = (in_memory.get_dictionary(), streaming.get_dictionary());
+    assert_eq!(
+        actual.iter_objects().collect::<Vec<_>>(),
+        expected.iter_objects().collect::<Vec<_>>()
+    );
+    assert_eq!(actual.object_partitions(), expected.object_partitions());
+    assert_eq!(actual.get_integer_range(INT, ..), None);
+    let range = actual.get_integer_range(INTEGER, ..).unwrap();
+    assert_eq!(
+        range
+            .map(|index| actual.get_integer(index))
+            .collect::<Vec<_>>(),
+        [Some(-3), Some(9), Some(10)]
+    );
+    assert_eq!(
+        common::sorted_triples(&streaming)?,
+        common::sorted_triples(&in_memory)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn legacy_layout_test() -> Result<(), Box<dyn Error>> {
+    // serialized before the objects were partitioned, so that all of them are
+    // stored in a single array sorted as strings
+    let mut legacy = Storage::new(MatrixLayout, Serialization::Zarr);
+    legacy.load(
+        Backend::FileSystem("resources/legacy.zarr"),
+        ThreadingStrategy::Single,
+    )?;
+    let store = Arc::new(MemoryStore::new());
+    let mut current = Storage::new(MatrixLayout, Serialization::Zarr);
+    current
+        .serialize(
+            Backend::Store(store.clone()),
+            "resources/rdf.nt",
+            ChunkingStrategy::Chunk,
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Single,
+        )?
+        .load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let mut expected = current.get_triples(None, None, None)?;
+    expected.sort();
+    let mut actual = legacy.get_triples(None, None, None)?;
+    actual.sort();
+    assert_eq!(actual, expected);
+
+    let dictionary = legacy.get_dictionary();
+    dictionary.iter_objects().for_each(|(index, object)| {
+        assert_eq!(dictionary.get_object_idx(&object), Some(index));
+        assert_eq!(
+            dictionary.get_object_kind(index),
+            Some(TermKind::of(&object))
+        );
+    });
+    let kinds = dictionary
+        .object_partitions()
+        .into_iter()
+        .map(|(kind, _)| kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        current
+            .get_dictionary()
+            .object_partitions()
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .filter(TermKind::is_literal)
+            .chain([TermKind::Iri])
+            .collect::<Vec<_>>()
+    );
+    let literals = legacy.get_triples_in_range(None, None, dictionary.literals())?;
+    assert_eq!(
+        literals.len(),
+        current
+            .get_triples_in_range(None, None, current.get_dictionary().literals())?
+            .len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+
+mod common;
+
+#[test]
+fn memory_write_read_test() {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage
+        .load(Backend::Memory(store), ThreadingStrategy::Single)
+        .unwrap();
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    )
+}
+
+#[test]
+fn memory_ops_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Memory(store), ThreadingStrategy::Single)?;
+
+    assert!(storage.contains(
+        common::Subject::Wilmslow.into(),
+        common::Predicate::Country.into(),
+        common::Object::UK.into(),
+    )?);
+    assert!(!storage.contains(
+        common::Subject::Alan.into(),
+        common::Predicate::Country.into(),
+        common::Object::UK.into(),
+    )?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn memory_async_load_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    // the in-memory store can only be accessed through the synchronous API
+    assert!(matches!(
+        storage.async_load(Backend::Memory(store)).await,
+        Err(RemoteHDTError::SyncBackend)
+    ));
+}
@@ -7,6 +7,7 @@ use remote_hdt::storage::params::Backend;
 use remote_hdt::storage::params::ChunkingStrategy;
 use remote_hdt::storage::params::ReferenceSystem;
 use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
 use remote_hdt::storage::Storage;
 use sprs::TriMat;
 use std::error::Error;
@@ -17,15 +18,10 @@ mod common;
 fn orientation_pso_matrix_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
 
-    common::setup(
-        common::PSO_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::PSO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::PSO_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_predicate(common::Predicate::InstanceOf.into())?
     {
         OpsFormat::Zarr(actual) => actual,
@@ -69,15 +65,10 @@ fn orientation_pso_matrix_test() -> Result<(), Box<dyn Error>> {
 fn orientation_ops_matrix_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
 
-    common::setup(
-        common::OPS_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::OPS,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::OPS);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::OPS_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_object(common::Object::Alan.into())?
     {
         OpsFormat::Zarr(actual) => actual,
@@ -106,15 +97,10 @@ fn orientation_ops_matrix_test() -> Result<(), Box<dyn Error>> {
 fn orientation_pso_tabular_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
 
-    common::setup(
-        common::TABULAR_PSO_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::PSO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::TABULAR_PSO_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_predicate(common::Predicate::InstanceOf.into())?
     {
         OpsFormat::SparseArray(actual) => actual,
@@ -153,15 +139,10 @@ fn orientation_pso_tabular_test() -> Result<(), Box<dyn Error>> {
 fn orientation_ops_tabular_test() -> Result<(), Box<dyn Error>> {
     let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
 
-    common::setup(
-        common::TABULAR_OPS_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::OPS,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::OPS);
 
     let actual = match storage
-        .load(Backend::FileSystem(common::TABULAR_OPS_ZARR))?
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)?
         .get_subject(common::Subject::Alan.into())?
     {
         OpsFormat::SparseArray(actual) => actual,
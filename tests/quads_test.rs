@@ -0,0 +1,270 @@
+use remote_hdt::error::OpsError;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::GraphPolicy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::fs;
+use std::io::Cursor;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+use zarrs::storage::ReadableStorageTraits;
+use zarrs::storage::StoreKey;
+
+const TRIG: &str = "@prefix ex: <http://example.org/> .
+    ex:bombe ex:discoverer ex:alan .
+    ex:people { ex:alan ex:instanceOf ex:Human . }
+    ex:places { ex:warrington ex:country ex:uk . ex:wilmslow ex:country ex:uk . }";
+
+fn exists(store: &MemoryStore, key: &str) -> bool {
+    store.get(&StoreKey::new(key).unwrap()).unwrap().is_some()
+}
+
+type Triple = (String, String, String);
+
+fn graph_triples<C>(storage: &mut Storage<C>, graph: &str) -> Result<Vec<Triple>, Box<dyn Error>> {
+    storage.select_graph(Some(graph))?;
+    let mut triples = storage.get_triples(None, None, None)?;
+    triples.sort();
+    Ok(triples)
+}
+
+fn triple(subject: &str, predicate: &str, object: &str) -> Triple {
+    (
+        format!("<http://example.org/{subject}>"),
+        format!("<http://example.org/{predicate}>"),
+        format!("<http://example.org/{object}>"),
+    )
+}
+
+#[test]
+fn merge_nquads_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        Cursor::new(fs::read("resources/rdf.nq")?),
+        RdfFormat::NQuads,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    // the graph names are dropped, while their triples are kept
+    assert!(!exists(&store, "group/partitions/0/zarr.json"));
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(storage.get_dictionary().subjects_size(), 4);
+    assert!(storage.contains(
+        "<http://example.org/wilmslow>",
+        "<http://example.org/country>",
+        "<http://example.org/uk>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn keep_trig_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+    let reader = Cursor::new(TRIG.as_bytes());
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_graph_policy(GraphPolicy::Keep);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        reader,
+        RdfFormat::TriG,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    assert!(exists(&store, "group/graphs/zarr.json"));
+    assert!(exists(&store, "group/partitions/0/zarr.json"));
+    assert!(exists(&store, "group/partitions/1/zarr.json"));
+    assert!(!exists(&store, "group/partitions/2/zarr.json"));
+
+    // the array containing every triple is left as it was
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(storage.get_dictionary().subjects_size(), 4);
+    assert!(storage.contains(
+        "<http://example.org/bombe>",
+        "<http://example.org/discoverer>",
+        "<http://example.org/alan>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn keep_streaming_nquads_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_graph_policy(GraphPolicy::Keep);
+    storage.set_memory_strategy(MemoryStrategy::Streaming(2));
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.nq",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    assert!(exists(&store, "group/partitions/0/zarr.json"));
+    assert!(exists(&store, "group/partitions/1/zarr.json"));
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let people = "<http://example.org/people>";
+    let places = "<http://example.org/places>";
+    assert_eq!(
+        graph_triples(&mut storage, people)?,
+        vec![
+            triple("alan", "instanceOf", "Human"),
+            triple("alan", "placeOfBirth", "warrington"),
+        ]
+    );
+    assert_eq!(
+        graph_triples(&mut storage, places)?,
+        vec![
+            triple("warrington", "country", "uk"),
+            triple("wilmslow", "country", "uk"),
+        ]
+    );
+
+    // the partitions match the ones built in memory
+    let in_memory_store = Arc::new(MemoryStore::new());
+    let mut in_memory = Storage::new(MatrixLayout, Serialization::Zarr);
+    in_memory.set_graph_policy(GraphPolicy::Keep);
+    in_memory.serialize(
+        Backend::Store(in_memory_store.clone()),
+        "resources/rdf.nq",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut in_memory = Storage::new(MatrixLayout, Serialization::Zarr);
+    in_memory.load(Backend::Store(in_memory_store), ThreadingStrategy::Single)?;
+
+    for graph in [people, places] {
+        assert_eq!(
+            graph_triples(&mut storage, graph)?,
+            graph_triples(&mut in_memory, graph)?
+        );
+    }
+
+    storage.select_graph(None)?;
+    assert!(storage.contains(
+        "<http://example.org/bombe>",
+        "<http://example.org/discoverer>",
+        "<http://example.org/alan>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn select_graph_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_graph_policy(GraphPolicy::Keep);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        Cursor::new(TRIG.as_bytes()),
+        RdfFormat::TriG,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    let dictionary = storage.get_dictionary();
+    assert_eq!(dictionary.graphs_size(), 2);
+    assert_eq!(
+        dictionary.get_graph_idx("<http://example.org/places>"),
+        Some(1)
+    );
+
+    storage.select_graph(Some("<http://example.org/places>"))?;
+    let mut triples = storage.get_triples(None, Some("<http://example.org/country>"), None)?;
+    triples.sort();
+    assert_eq!(
+        triples,
+        vec![
+            (
+                "<http://example.org/warrington>".to_string(),
+                "<http://example.org/country>".to_string(),
+                "<http://example.org/uk>".to_string(),
+            ),
+            (
+                "<http://example.org/wilmslow>".to_string(),
+                "<http://example.org/country>".to_string(),
+                "<http://example.org/uk>".to_string(),
+            ),
+        ]
+    );
+    assert!(!storage.contains(
+        "<http://example.org/alan>",
+        "<http://example.org/instanceOf>",
+        "<http://example.org/Human>",
+    )?);
+
+    // the default graph contains the triples of every graph
+    storage.select_graph(None)?;
+    assert!(storage.contains(
+        "<http://example.org/alan>",
+        "<http://example.org/instanceOf>",
+        "<http://example.org/Human>",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn select_sparse_graph_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_graph_policy(GraphPolicy::Keep);
+    storage.serialize_reader(
+        Backend::Store(store.clone()),
+        Cursor::new(TRIG.as_bytes()),
+        RdfFormat::TriG,
+        ChunkingStrategy::Sharding(2),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    storage.select_graph(Some("<http://example.org/people>"))?;
+    assert_eq!(storage.get_triples(None, None, None)?.len(), 1);
+    assert!(matches!(
+        storage.select_graph(Some("<http://example.org/machines>")),
+        Err(RemoteHDTError::Ops(OpsError::GraphNotFound))
+    ));
+
+    Ok(())
+}
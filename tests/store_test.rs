@@ -0,0 +1,101 @@
+use object_store::local::LocalFileSystem;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::ops::AsyncOps;
+use remote_hdt::storage::ops::Ops;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::sync::Arc;
+use zarrs::storage::store::AsyncObjectStore;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+#[test]
+fn memory_store_write_read_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.nt",
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.load(Backend::Store(store), ThreadingStrategy::Single)?;
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn memory_store_exists_test() -> Result<(), Box<dyn Error>> {
+    let store = Arc::new(MemoryStore::new());
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.serialize(
+        Backend::Store(store.clone()),
+        "resources/rdf.nt",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    )?;
+
+    // the store is not empty anymore; hence, it cannot be overwritten
+    let result = storage.serialize(
+        Backend::Store(store),
+        "resources/rdf.nt",
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    );
+    assert!(matches!(result, Err(RemoteHDTError::PathExists)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn object_store_read_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+
+    let (_dir, path) =
+        common::fs_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::PSO);
+
+    let expected = storage
+        .load(Backend::FileSystem(&path), ThreadingStrategy::Single)?
+        .get_triples(Some(common::Subject::Alan.into()), None, None)?;
+
+    // any implementation of the object_store API can be used as a backend;
+    // here, the local file system stands in for a remote object storage
+    let store = Arc::new(AsyncObjectStore::new(LocalFileSystem::new_with_prefix(
+        path,
+    )?));
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    let actual = storage
+        .async_load(Backend::AsyncStore(store.clone()))
+        .await?
+        .async_get_triples(Some(common::Subject::Alan.into()), None, None)
+        .await?;
+
+    assert_eq!(actual, expected);
+    assert!(matches!(
+        storage.load(Backend::AsyncStore(store), ThreadingStrategy::Single),
+        Err(RemoteHDTError::AsyncBackend)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::MemoryStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+
+mod common;
+
+#[test]
+fn streaming_matrix_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    storage.set_memory_strategy(MemoryStrategy::Streaming(2));
+
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+
+    storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
+        .unwrap();
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    )
+}
+
+#[test]
+fn streaming_tabular_test() {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+    storage.set_memory_strategy(MemoryStrategy::Streaming(3));
+
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(4),
+        ReferenceSystem::SPO,
+    );
+
+    storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
+        .unwrap();
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    )
+}
+
+#[test]
+fn single_pass_matches_streaming_test() {
+    // the in-memory parser reads the dump once, while the streaming one reads
+    // it twice; both of them must assign the same indices to the terms
+    let mut in_memory = Storage::new(MatrixLayout, Serialization::Sparse);
+    let in_memory_store = common::memory_setup(
+        &mut in_memory,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::OPS,
+    );
+
+    let mut streaming = Storage::new(MatrixLayout, Serialization::Sparse);
+    streaming.set_memory_strategy(MemoryStrategy::Streaming(2));
+    let streaming_store = common::memory_setup(
+        &mut streaming,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::OPS,
+    );
+
+    in_memory
+        .load(Backend::Store(in_memory_store), ThreadingStrategy::Single)
+        .unwrap();
+    streaming
+        .load(Backend::Store(streaming_store), ThreadingStrategy::Single)
+        .unwrap();
+
+    let (expected, actual) = (streaming.get_dictionary(), in_memory.get_dictionary());
+    assert_eq!(
+        actual.iter_subjects().collect::<Vec<_>>(),
+        expected.iter_subjects().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        actual.iter_predicates().collect::<Vec<_>>(),
+        expected.iter_predicates().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        actual.iter_objects().collect::<Vec<_>>(),
+        expected.iter_objects().collect::<Vec<_>>()
+    );
+    assert_eq!(in_memory.get_sparse_array(), streaming.get_sparse_array())
+}
@@ -0,0 +1,86 @@
+use remote_hdt::error::ParserError;
+use remote_hdt::error::RemoteHDTError;
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ErrorStrategy;
+use remote_hdt::storage::params::RdfFormat;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::error::Error;
+use std::io;
+use std::io::BufReader;
+use std::io::Cursor;
+use std::io::Read;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+
+mod common;
+
+#[test]
+fn strict_syntax_error_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_error_strategy(ErrorStrategy::Strict);
+
+    match common::serialize_into_memory(&mut storage, "resources/malformed.nt") {
+        Err(RemoteHDTError::RdfParse(ParserError::Syntax(err))) => assert_eq!(err.line, 2),
+        _ => panic!("the malformed statement should have been reported"),
+    }
+}
+
+#[test]
+fn lenient_syntax_error_test() -> Result<(), Box<dyn Error>> {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_error_strategy(ErrorStrategy::Lenient(1));
+    common::serialize_into_memory(&mut storage, "resources/malformed.nt")?;
+
+    // both invalid statements are counted, while only the first is detailed
+    let report = storage.get_parse_report();
+    assert_eq!(report.skipped, 2);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].line, 2);
+    assert_eq!(storage.get_dictionary().subjects_size(), 2);
+
+    Ok(())
+}
+
+/// A reader whose connection is lost right after the first statement, which
+/// makes the parser return the same error on every step
+struct Disconnected;
+
+impl Read for Disconnected {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("connection reset"))
+    }
+}
+
+#[test]
+fn persistent_syntax_error_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Zarr);
+    storage.set_error_strategy(ErrorStrategy::Lenient(10));
+
+    // the parser cannot recover from the error; hence, the serialization must
+    // fail instead of skipping the same error forever
+    let reader = BufReader::new(
+        Cursor::new(
+            "<http://example.org/alan> <http://example.org/instanceOf> <http://example.org/Human> .\n"
+                .as_bytes(),
+        )
+        .chain(Disconnected),
+    );
+    let result = storage.serialize_reader(
+        Backend::Store(Arc::new(MemoryStore::new())),
+        reader,
+        RdfFormat::Turtle,
+        ChunkingStrategy::Chunk,
+        ReferenceSystem::SPO,
+        ThreadingStrategy::Single,
+    );
+
+    assert!(matches!(
+        result,
+        Err(RemoteHDTError::RdfParse(ParserError::Syntax(error))) if error.message == "connection reset"
+    ));
+}
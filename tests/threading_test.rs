@@ -0,0 +1,94 @@
+use remote_hdt::storage::layout::matrix::MatrixLayout;
+use remote_hdt::storage::layout::tabular::TabularLayout;
+use remote_hdt::storage::params::Backend;
+use remote_hdt::storage::params::ChunkingStrategy;
+use remote_hdt::storage::params::ReferenceSystem;
+use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
+use remote_hdt::storage::Storage;
+use std::sync::Arc;
+use zarrs::storage::store::MemoryStore;
+use zarrs::storage::ListableStorageTraits;
+use zarrs::storage::ReadableStorageTraits;
+
+mod common;
+
+fn read_shards(store: &MemoryStore) -> Vec<(String, Vec<u8>)> {
+    let mut shards = store
+        .list()
+        .unwrap()
+        .into_iter()
+        .filter(|key| key.as_str().starts_with("group/RemoteHDT/c/"))
+        .map(|key| (key.as_str().to_string(), store.get(&key).unwrap().unwrap()))
+        .collect::<Vec<_>>();
+    shards.sort();
+    shards
+}
+
+#[test]
+fn multi_threaded_write_read_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    let store = Arc::new(MemoryStore::new());
+
+    storage
+        .serialize(
+            Backend::Store(store.clone()),
+            "resources/rdf.nt",
+            ChunkingStrategy::Sharding(3),
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Multi(4),
+        )
+        .unwrap();
+
+    storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Multi(4))
+        .unwrap();
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    )
+}
+
+#[test]
+fn multi_threaded_deterministic_test() {
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+
+    let store = common::memory_setup(
+        &mut storage,
+        ChunkingStrategy::Sharding(3),
+        ReferenceSystem::SPO,
+    );
+
+    let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
+    let multi_threaded = Arc::new(MemoryStore::new());
+    storage
+        .serialize(
+            Backend::Store(multi_threaded.clone()),
+            "resources/rdf.nt",
+            ChunkingStrategy::Sharding(3),
+            ReferenceSystem::SPO,
+            ThreadingStrategy::Multi(3),
+        )
+        .unwrap();
+
+    assert_eq!(read_shards(&multi_threaded), read_shards(&store));
+}
+
+#[test]
+fn multi_threaded_load_tabular_test() {
+    let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
+
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
+
+    // every triple is stored in its own shard; hence, each of the threads
+    // retrieves several shards, which are then merged
+    storage
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Multi(4))
+        .unwrap();
+
+    assert_eq!(
+        storage.get_sparse_array().unwrap(),
+        common::Graph::new(&storage.get_dictionary())
+    )
+}
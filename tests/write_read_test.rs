@@ -4,6 +4,7 @@ use remote_hdt::storage::params::Backend;
 use remote_hdt::storage::params::ChunkingStrategy;
 use remote_hdt::storage::params::ReferenceSystem;
 use remote_hdt::storage::params::Serialization;
+use remote_hdt::storage::params::ThreadingStrategy;
 use remote_hdt::storage::Storage;
 
 mod common;
@@ -12,15 +13,10 @@ mod common;
 fn write_read_tabular_test() {
     let mut storage = Storage::new(TabularLayout, Serialization::Sparse);
 
-    common::setup(
-        common::TABULAR_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::SPO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
 
     storage
-        .load(Backend::FileSystem(common::TABULAR_ZARR))
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
         .unwrap();
 
     assert_eq!(
@@ -33,15 +29,10 @@ fn write_read_tabular_test() {
 fn write_read_matrix_test() {
     let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
 
-    common::setup(
-        common::MATRIX_ZARR,
-        &mut storage,
-        ChunkingStrategy::Chunk,
-        ReferenceSystem::SPO,
-    );
+    let store = common::memory_setup(&mut storage, ChunkingStrategy::Chunk, ReferenceSystem::SPO);
 
     storage
-        .load(Backend::FileSystem(common::MATRIX_ZARR))
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
         .unwrap();
 
     assert_eq!(
@@ -54,15 +45,14 @@ fn write_read_matrix_test() {
 fn write_read_matrix_sharding_test() {
     let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
 
-    common::setup(
-        common::SHARDING_ZARR,
+    let store = common::memory_setup(
         &mut storage,
         ChunkingStrategy::Sharding(3),
         ReferenceSystem::SPO,
     );
 
     storage
-        .load(Backend::FileSystem(common::SHARDING_ZARR))
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
         .unwrap();
 
     assert_eq!(
@@ -75,15 +65,14 @@ fn write_read_matrix_sharding_test() {
 fn write_read_larger_than_triples_shard_test() {
     let mut storage = Storage::new(MatrixLayout, Serialization::Sparse);
 
-    common::setup(
-        common::LARGER_ZARR,
+    let store = common::memory_setup(
         &mut storage,
         ChunkingStrategy::Sharding(10000),
         ReferenceSystem::SPO,
     );
 
     storage
-        .load(Backend::FileSystem(common::LARGER_ZARR))
+        .load(Backend::Store(store.clone()), ThreadingStrategy::Single)
         .unwrap();
 
     assert_eq!(